@@ -1,50 +1,84 @@
 use bevy::{
     prelude::*,
-    render::{
-        camera::Camera,
-        view::ColorGrading,
-    },
-    core_pipeline::bloom::Bloom,
+    render::view::ColorGrading,
+    core_pipeline::{bloom::Bloom, motion_blur::MotionBlur},
 };
+use crate::components::FollowCamera;
+use crate::constants::{
+    BLOOM_INTENSITY, BOOST_BLOOM_INTENSITY, BOOST_BLOOM_RESPONSE_TIME, BOOST_SPEED_FACTOR,
+    MOTION_BLUR_MAX_SHUTTER_ANGLE, MOTION_BLUR_SAMPLES,
+};
+use crate::resources::{FlightConfig, PlaneState};
+use crate::util::smoothing;
 
 pub struct AtmosphericFogPlugin;
 
 impl Plugin for AtmosphericFogPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, setup_atmospheric_fog);
+        // The flight camera is spawned when the world is built, not at startup
+        app.add_systems(Update, setup_atmospheric_fog);
     }
 }
 
 #[derive(Component)]
 pub struct AtmosphericFog;
 
+// Fade distant islands and clouds into haze on the flight camera. The UI camera
+// is left alone. update_daylight recolors the haze through the day.
 fn setup_atmospheric_fog(
     mut commands: Commands,
-    mut camera_query: Query<Entity, With<Camera>>,
+    flight_config: Res<FlightConfig>,
+    camera_query: Query<Entity, Added<FollowCamera>>,
 ) {
-    // Add atmospheric fog to the camera
-    for camera_entity in camera_query.iter_mut() {
-        // In Bevy 0.15.3, we'll just add some basic color grading
-        // since the fog API might be different
-        commands.entity(camera_entity).insert(
-            ColorGrading {
-                global: Default::default(),
-                shadows: Default::default(),
-                midtones: Default::default(),
-                highlights: Default::default(),
+    for camera_entity in camera_query.iter() {
+        let [red, green, blue] = flight_config.fog_color;
+        commands.entity(camera_entity).insert((
+            DistanceFog {
+                color: Color::srgb(red, green, blue),
+                falloff: FogFalloff::from_visibility(flight_config.fog_visibility),
+                ..default()
             },
-        );
+            ColorGrading::default(),
+            AtmosphericFog,
+        ));
     }
 }
 
-// Function to add motion blur to a camera
+// Add camera motion blur, starting with no blur; `speed_motion_blur` sets its strength.
+// MotionBlur brings in the depth and motion vector prepasses it needs.
 pub fn add_motion_blur(
     commands: &mut Commands,
     camera_entity: Entity,
 ) {
-    // In Bevy 0.15.3, we'll add bloom instead since motion blur API might be different
-    commands.entity(camera_entity).insert(Bloom {
-        intensity: 0.15,
-        ..default()
+    commands.entity(camera_entity).insert(MotionBlur {
+        shutter_angle: 0.0,
+        samples: MOTION_BLUR_SAMPLES,
     });
 }
+
+// Blur more the faster the plane flies, from none at the minimum airspeed to the
+// strongest at the boosted top speed. Does nothing when motion blur is off.
+pub fn speed_motion_blur(
+    plane_state: Res<PlaneState>,
+    flight_config: Res<FlightConfig>,
+    mut camera_query: Query<&mut MotionBlur, With<FollowCamera>>,
+) {
+    let top_speed = flight_config.max_airspeed * BOOST_SPEED_FACTOR;
+    let t = ((plane_state.speed - flight_config.min_airspeed) / (top_speed - flight_config.min_airspeed)).clamp(0.0, 1.0);
+    for mut motion_blur in camera_query.iter_mut() {
+        motion_blur.shutter_angle = MOTION_BLUR_MAX_SHUTTER_ANGLE * t;
+    }
+}
+
+// Glow brighter while boosting, easing in and out. Does nothing when bloom is off.
+pub fn boost_bloom(
+    plane_state: Res<PlaneState>,
+    time: Res<Time>,
+    mut camera_query: Query<&mut Bloom, With<FollowCamera>>,
+) {
+    let target = if plane_state.boosting { BOOST_BLOOM_INTENSITY } else { BLOOM_INTENSITY };
+    let alpha = smoothing(BOOST_BLOOM_RESPONSE_TIME, time.delta_secs());
+    for mut bloom in camera_query.iter_mut() {
+        bloom.intensity += (target - bloom.intensity) * alpha;
+    }
+}
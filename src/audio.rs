@@ -0,0 +1,107 @@
+use bevy::{
+    prelude::*,
+    asset::io::file::FileAssetReader,
+    audio::Volume,
+};
+use avian3d::prelude::*;
+use crate::components::{Plane, Water, EngineSound, WindSound};
+use crate::constants::*;
+use crate::resources::{PlaneState, FlightConfig};
+use crate::events::WaterImpact;
+
+pub const ENGINE_SOUND_PATH: &str = "audio/engine.ogg";
+pub const WIND_SOUND_PATH: &str = "audio/wind.ogg";
+pub const SPLASH_SOUND_PATH: &str = "audio/splash.ogg";
+
+// Whether a file exists in the assets folder. Sounds are optional, so a missing
+// file just means that layer stays silent instead of logging load errors.
+fn asset_exists(path: &str) -> bool {
+    FileAssetReader::get_base_path().join("assets").join(path).is_file()
+}
+
+// Start the looping engine and wind sounds, silent until update_flight_audio sets them
+pub fn setup_audio(mut commands: Commands, asset_server: Res<AssetServer>) {
+    spawn_sound_loop(&mut commands, &asset_server, ENGINE_SOUND_PATH, EngineSound);
+    spawn_sound_loop(&mut commands, &asset_server, WIND_SOUND_PATH, WindSound);
+}
+
+fn spawn_sound_loop(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    path: &str,
+    marker: impl Component,
+) {
+    if !asset_exists(path) {
+        info!("No sound at assets/{}, playing without it", path);
+        return;
+    }
+
+    commands.spawn((
+        AudioPlayer::<AudioSource>(asset_server.load(path)),
+        PlaybackSettings::LOOP.with_volume(Volume::new(0.0)),
+        marker,
+    ));
+}
+
+// Engine pitch and volume follow the throttle, dropping to idle when sailing on the water.
+// The wind gets louder with the plane's actual speed through the air.
+pub fn update_flight_audio(
+    plane_state: Res<PlaneState>,
+    flight_config: Res<FlightConfig>,
+    plane_query: Query<(&LinearVelocity, &CollidingEntities), With<Plane>>,
+    water_query: Query<Entity, With<Water>>,
+    engine_query: Query<&AudioSink, (With<EngineSound>, Without<WindSound>)>,
+    wind_query: Query<&AudioSink, (With<WindSound>, Without<EngineSound>)>,
+) {
+    let Ok((linear_vel, colliding_entities)) = plane_query.get_single() else {
+        return;
+    };
+    let is_on_water = water_query
+        .get_single()
+        .is_ok_and(|water_entity| colliding_entities.contains(&water_entity));
+
+    if let Ok(engine) = engine_query.get_single() {
+        let idling = is_on_water && plane_state.speed < flight_config.water_stop_threshold;
+        let (speed, volume) = if idling {
+            (ENGINE_IDLE_PITCH, ENGINE_IDLE_VOLUME)
+        } else {
+            let throttle = ((plane_state.speed - flight_config.min_airspeed)
+                / (flight_config.max_airspeed - flight_config.min_airspeed))
+                .clamp(0.0, 1.0);
+            (
+                ENGINE_MIN_PITCH.lerp(ENGINE_MAX_PITCH, throttle),
+                ENGINE_MIN_VOLUME.lerp(ENGINE_MAX_VOLUME, throttle),
+            )
+        };
+        engine.set_speed(speed);
+        engine.set_volume(volume);
+    }
+
+    if let Ok(wind) = wind_query.get_single() {
+        let airflow = (linear_vel.0.length() / flight_config.max_airspeed).clamp(0.0, 1.0);
+        wind.set_speed(0.8 + airflow * 0.4);
+        wind.set_volume(airflow * airflow * WIND_MAX_VOLUME);
+    }
+}
+
+// One-shot splash on a hard water impact, louder the harder the hit
+pub fn play_splash_sound(
+    mut commands: Commands,
+    mut impacts: EventReader<WaterImpact>,
+    asset_server: Res<AssetServer>,
+    mut available: Local<Option<bool>>,
+) {
+    let available = *available.get_or_insert_with(|| asset_exists(SPLASH_SOUND_PATH));
+
+    for impact in impacts.read() {
+        if !available {
+            continue;
+        }
+
+        let volume = (impact.velocity / SPLASH_FULL_VOLUME_SPEED).clamp(SPLASH_MIN_VOLUME, 1.0);
+        commands.spawn((
+            AudioPlayer::<AudioSource>(asset_server.load(SPLASH_SOUND_PATH)),
+            PlaybackSettings::DESPAWN.with_volume(Volume::new(volume)),
+        ));
+    }
+}
@@ -0,0 +1,127 @@
+use bevy::prelude::*;
+use rand::Rng;
+use crate::components::{Bird, Particle, Plane};
+use crate::constants::*;
+use crate::environment_systems::wrap_coordinate;
+use crate::particles::{spawn_particle, ParticleAssets};
+use crate::resources::{PlaneState, WorldConfig, GameRng};
+
+// Scatter loose flocks around the sky. Birds in a flock share a course and only
+// differ slightly in their wander phase, so they drift about without splitting up.
+pub fn spawn_birds(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    world_config: &WorldConfig,
+    rng: &mut impl Rng,
+) {
+    let bird_mesh = meshes.add(Cuboid::new(1.2, 0.1, 0.4));
+    let bird_material = materials.add(StandardMaterial {
+        base_color: Color::srgb(0.15, 0.15, 0.18),
+        perceptual_roughness: 1.0,
+        ..default()
+    });
+
+    let range = world_config.spawn_radius;
+    for _ in 0..BIRD_FLOCK_COUNT {
+        let center = Vec3::new(
+            rng.gen_range(-range..range),
+            rng.gen_range(BIRD_MIN_ALTITUDE..BIRD_MAX_ALTITUDE),
+            rng.gen_range(-range..range),
+        );
+        let heading = rng.gen_range(0.0..std::f32::consts::TAU);
+        let phase = rng.gen_range(0.0..std::f32::consts::TAU);
+
+        for _ in 0..BIRDS_PER_FLOCK {
+            let offset = Vec3::new(
+                rng.gen_range(-BIRD_FLOCK_SPREAD..BIRD_FLOCK_SPREAD),
+                rng.gen_range(-BIRD_FLOCK_SPREAD..BIRD_FLOCK_SPREAD) * 0.3,
+                rng.gen_range(-BIRD_FLOCK_SPREAD..BIRD_FLOCK_SPREAD),
+            );
+            commands.spawn((
+                Mesh3d(bird_mesh.clone()),
+                MeshMaterial3d(bird_material.clone()),
+                Transform::from_translation(center + offset),
+                Bird {
+                    heading,
+                    phase: phase + rng.gen_range(-0.3..0.3),
+                },
+            ));
+        }
+    }
+}
+
+// Fly each bird along a gently weaving course, bobbing and flapping,
+// and wrap them around the world edge like the clouds
+pub fn bird_movement(
+    time: Res<Time>,
+    world_config: Res<WorldConfig>,
+    mut bird_query: Query<(&mut Transform, &Bird)>,
+) {
+    let t = time.elapsed_secs();
+    let dt = time.delta_secs();
+    let wrap_bound = world_config.spawn_radius;
+
+    for (mut transform, bird) in bird_query.iter_mut() {
+        let heading = bird.heading + BIRD_WEAVE * (t * 0.2 + bird.phase).sin();
+        let direction = Vec3::new(heading.sin(), 0.0, heading.cos());
+        transform.translation += direction * BIRD_SPEED * dt;
+        transform.translation.y += (t * 0.8 + bird.phase).cos() * 0.5 * dt;
+
+        if let Some(wrapped) = wrap_coordinate(transform.translation.x, wrap_bound) {
+            transform.translation.x = wrapped;
+        }
+        if let Some(wrapped) = wrap_coordinate(transform.translation.z, wrap_bound) {
+            transform.translation.z = wrapped;
+        }
+
+        // Face along the course and rock the wings to suggest flapping
+        let flap = (t * BIRD_FLAP_RATE + bird.phase * 5.0).sin() * 0.4;
+        transform.rotation = Quat::from_rotation_y(heading) * Quat::from_rotation_z(flap);
+    }
+}
+
+// Birds are soft obstacles: flying into one costs a little speed and scatters feathers
+pub fn bird_strikes(
+    mut commands: Commands,
+    mut plane_state: ResMut<PlaneState>,
+    particle_assets: Res<ParticleAssets>,
+    mut game_rng: ResMut<GameRng>,
+    plane_query: Query<&Transform, With<Plane>>,
+    bird_query: Query<(Entity, &Transform), With<Bird>>,
+) {
+    let Ok(plane_transform) = plane_query.get_single() else {
+        return;
+    };
+
+    let rng = game_rng.rng();
+    for (entity, bird_transform) in bird_query.iter() {
+        if bird_transform.translation.distance(plane_transform.translation) > BIRD_HIT_RADIUS {
+            continue;
+        }
+
+        plane_state.speed *= BIRD_HIT_SPEED_FACTOR;
+        commands.entity(entity).despawn();
+
+        for _ in 0..BIRD_FEATHER_COUNT {
+            let drift = Vec3::new(
+                rng.gen_range(-1.0..1.0),
+                rng.gen_range(-0.5..1.0),
+                rng.gen_range(-1.0..1.0),
+            );
+            spawn_particle(
+                &mut commands,
+                &particle_assets.mesh,
+                &particle_assets.feather,
+                bird_transform.translation,
+                Particle {
+                    velocity: drift * 3.0,
+                    lifetime: Timer::from_seconds(rng.gen_range(1.0..2.0), TimerMode::Once),
+                    start_size: 0.3,
+                    end_size: 0.2,
+                    gravity: 1.0,
+                },
+            );
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use bevy::prelude::*;
+use rand::Rng;
+use std::collections::HashMap;
+use crate::components::{Plane, Water};
+use crate::constants::{CHUNK_SIZE, CHUNK_LOAD_RADIUS, CHUNK_UNLOAD_RADIUS, WATER_SUBDIVISIONS};
+use crate::resources::{WorldConfig, WorldSeed, GraphicsQuality};
+use crate::setup::{WorldAssets, spawn_island, spawn_cloud};
+
+// Populated chunks of the endless ocean by grid cell, with everything spawned in each
+// so it can be cleared again
+#[derive(Resource, Default)]
+pub struct ChunkManager {
+    pub loaded: HashMap<IVec2, Vec<Entity>>,
+}
+
+// Grid cell of the chunk containing a position
+pub fn chunk_cell(position: Vec3) -> IVec2 {
+    IVec2::new(
+        (position.x / CHUNK_SIZE).floor() as i32,
+        (position.z / CHUNK_SIZE).floor() as i32,
+    )
+}
+
+// Rounded-down `expected` plus one more with the leftover fraction as the chance,
+// so the count averages out to `expected`
+fn random_count(expected: f32, rng: &mut impl Rng) -> usize {
+    expected.floor() as usize + usize::from(rng.gen_bool(expected.fract() as f64))
+}
+
+// Populate the chunks around the plane with islands and clouds as it flies, and clear
+// the ones it has left behind. Only runs in the endless ocean.
+#[allow(clippy::too_many_arguments)]
+pub fn stream_chunks(
+    mut commands: Commands,
+    mut chunk_manager: ResMut<ChunkManager>,
+    world_config: Res<WorldConfig>,
+    world_seed: Res<WorldSeed>,
+    graphics_quality: Res<GraphicsQuality>,
+    world_assets: Option<Res<WorldAssets>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    plane_query: Query<&Transform, With<Plane>>,
+) {
+    let (true, Some(world_assets), Ok(plane_transform)) =
+        (world_config.endless, world_assets, plane_query.get_single())
+    else {
+        return;
+    };
+    let center = chunk_cell(plane_transform.translation);
+
+    chunk_manager.loaded.retain(|cell, entities| {
+        let keep = (*cell - center).abs().max_element() <= CHUNK_UNLOAD_RADIUS;
+        if !keep {
+            for entity in entities.drain(..) {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+        keep
+    });
+
+    let chunk_area = CHUNK_SIZE * CHUNK_SIZE;
+    let islands_per_chunk = world_config.density_scaled(world_config.island_count, chunk_area);
+    let clouds_per_chunk = world_config.density_scaled(graphics_quality.cloud_count(world_config.cloud_count), chunk_area);
+
+    for dz in -CHUNK_LOAD_RADIUS..=CHUNK_LOAD_RADIUS {
+        for dx in -CHUNK_LOAD_RADIUS..=CHUNK_LOAD_RADIUS {
+            let cell = center + IVec2::new(dx, dz);
+            if chunk_manager.loaded.contains_key(&cell) {
+                continue;
+            }
+
+            let rng = &mut world_seed.chunk_rng(cell);
+            let min = cell.as_vec2() * CHUNK_SIZE;
+            let (x_range, z_range) = (min.x..min.x + CHUNK_SIZE, min.y..min.y + CHUNK_SIZE);
+            let mut entities = Vec::new();
+            for _ in 0..random_count(islands_per_chunk, rng) {
+                entities.extend(spawn_island(&mut commands, &world_assets, x_range.clone(), z_range.clone(), rng));
+            }
+            for _ in 0..random_count(clouds_per_chunk, rng) {
+                entities.extend(spawn_cloud(&mut commands, &mut materials, &world_assets, *graphics_quality, x_range.clone(), z_range.clone(), rng));
+            }
+            chunk_manager.loaded.insert(cell, entities);
+        }
+    }
+}
+
+// Keep the water under the plane in the endless ocean. It moves in whole steps of the
+// wave mesh's grid so the swells, sampled at world positions, don't swim.
+pub fn recenter_water(
+    world_config: Res<WorldConfig>,
+    plane_query: Query<&Transform, (With<Plane>, Without<Water>)>,
+    mut water_query: Query<&mut Transform, With<Water>>,
+) {
+    let (true, Ok(plane_transform), Ok(mut water_transform)) =
+        (world_config.endless, plane_query.get_single(), water_query.get_single_mut())
+    else {
+        return;
+    };
+
+    let step = world_config.water_visual_size / (WATER_SUBDIVISIONS + 1) as f32;
+    let snapped = (plane_transform.translation / step).round() * step;
+    let target = Vec3::new(snapped.x, water_transform.translation.y, snapped.z);
+    if water_transform.translation != target {
+        water_transform.translation = target;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+    use crate::components::{Cloud, Island};
+
+    // Islands and clouds streamed in around a plane at `position` in an endless ocean
+    // built from `seed`, as sorted translations
+    fn streamed_world(seed: u64, position: Vec3) -> (Vec<[f32; 3]>, Vec<[f32; 3]>) {
+        let mut world = World::new();
+        let mut meshes = Assets::<Mesh>::default();
+        let mut materials = Assets::<StandardMaterial>::default();
+        let mut images = Assets::<Image>::default();
+        let world_assets = WorldAssets::new(&mut meshes, &mut materials, &mut images, true);
+        let mut world_config = WorldConfig::with_size(1000.0);
+        world_config.endless = true;
+        world.insert_resource(world_config);
+        world.insert_resource(WorldSeed::new(seed));
+        world.insert_resource(GraphicsQuality::default());
+        world.insert_resource(world_assets);
+        world.insert_resource(materials);
+        world.init_resource::<ChunkManager>();
+        world.spawn((Plane, Transform::from_translation(position)));
+
+        world.run_system_once(stream_chunks).unwrap();
+
+        let mut island_query = world.query_filtered::<&Transform, With<Island>>();
+        let mut cloud_query = world.query_filtered::<&Transform, With<Cloud>>();
+        let sorted = |transforms: Vec<&Transform>| {
+            let mut translations: Vec<[f32; 3]> = transforms.iter().map(|t| t.translation.to_array()).collect();
+            translations.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            translations
+        };
+        (
+            sorted(island_query.iter(&world).collect()),
+            sorted(cloud_query.iter(&world).collect()),
+        )
+    }
+
+    #[test]
+    fn same_seed_streams_the_same_islands_and_clouds() {
+        let position = Vec3::new(2.5 * CHUNK_SIZE, 50.0, -1.5 * CHUNK_SIZE);
+        let (islands, clouds) = streamed_world(42, position);
+        assert!(!islands.is_empty() && !clouds.is_empty());
+        assert_eq!(streamed_world(42, position), (islands.clone(), clouds));
+        assert_ne!(streamed_world(43, position).0, islands);
+    }
+
+    #[test]
+    fn chunk_generator_depends_only_on_seed_and_cell() {
+        let world_seed = WorldSeed::new(7);
+        let draw = |cell: IVec2| world_seed.chunk_rng(cell).gen_range(0..u64::MAX);
+        assert_eq!(draw(IVec2::new(3, -2)), WorldSeed::new(7).chunk_rng(IVec2::new(3, -2)).gen_range(0..u64::MAX));
+        assert_ne!(draw(IVec2::new(3, -2)), draw(IVec2::new(-2, 3)));
+        assert_ne!(draw(IVec2::new(3, -2)), WorldSeed::new(8).chunk_rng(IVec2::new(3, -2)).gen_range(0..u64::MAX));
+    }
+}
@@ -1,27 +1,274 @@
 use bevy::prelude::*;
+use crate::constants::HIGH_CLOUD_WIND_FACTOR;
 
 // Plane-related components
 #[derive(Component)]
 pub struct Plane;
 
+#[derive(Component)]
+pub struct Propeller;
+
+// An AI plane that hunts the player, flying at `speed` and turning no faster than
+// `turn_rate` radians per second
+#[derive(Component)]
+pub struct EnemyPlane {
+    pub speed: f32,
+    pub turn_rate: f32,
+}
+
 #[derive(Component)]
 pub struct FollowCamera;
 
+// The see-through plane that flies back a recorded flight
+#[derive(Component)]
+pub struct GhostPlane;
+
+// Put on the flight camera while it flies free of the plane (F6). Yaw and pitch
+// are the mouse-look angles; camera_follow leaves a camera with this alone.
+#[derive(Component)]
+pub struct SpectatorCamera {
+    pub yaw: f32,
+    pub pitch: f32,
+}
+
+// Lighting components
+// The main shadow-casting light, moved across the sky by the day/night cycle
+#[derive(Component)]
+pub struct Sun;
+
+// Sphere around the camera shading the sky from the horizon up to the zenith color
+#[derive(Component)]
+pub struct SkyDome;
+
+// Softer secondary light that dims with the daylight
+#[derive(Component)]
+pub struct FillLight;
+
 // Environment components
 #[derive(Component)]
-pub struct Island;
+pub struct Island {
+    pub kind: IslandKind,
+}
+
+// Full and low-detail meshes of an island, swapped by distance from the plane
+#[derive(Component)]
+pub struct IslandLod {
+    pub detailed: Handle<Mesh>,
+    pub simple: Handle<Mesh>,
+}
+
+// Island archetypes, each with its own terrain profile (see `setup::island_shape`)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IslandKind {
+    Atoll, // Low, wide and mostly beach
+    Rocky, // Tall, narrow bare peak
+    Sloped, // Grassy hill rising gently from the beach
+}
+
+impl IslandKind {
+    pub const ALL: [Self; 3] = [Self::Atoll, Self::Rocky, Self::Sloped];
+
+    // Radius of the unscaled island where its beach meets the water
+    pub fn footprint_radius(self) -> f32 {
+        match self {
+            Self::Atoll => 14.0,
+            Self::Rocky => 9.0,
+            Self::Sloped => 14.0,
+        }
+    }
+
+    // Height of the unscaled island's peak above the waterline
+    pub fn top_height(self) -> f32 {
+        match self {
+            Self::Atoll => 2.0,
+            Self::Rocky => 24.0,
+            Self::Sloped => 10.0,
+        }
+    }
+}
+
+// Invisible column of rising air over an island. Lifts the plane anywhere inside
+// `radius` of `position` (measured horizontally) up to THERMAL_TOP_ALTITUDE.
+#[derive(Component)]
+pub struct Thermal {
+    pub position: Vec3,
+    pub radius: f32,
+    pub strength: f32, // Upward acceleration at the center of the column
+}
+
+// The island to land at in landing mode
+#[derive(Component)]
+pub struct LandingTarget;
+
+// A ring on the course; `index` is its place in the flying order
+#[derive(Component)]
+pub struct Ring {
+    pub radius: f32,
+    pub index: usize,
+}
 
 #[derive(Component)]
 pub struct Cloud {
     pub speed: f32,
+    pub size: Vec3, // Full-size scale; shrunk near the edge of the world to hide wrapping
+    pub layer: CloudLayer,
+}
+
+// One camera-facing quad of a billboard cloud. `offset` is its place in the cloud's unit
+// box and `size` its width as a share of the cloud's horizontal size.
+#[derive(Component)]
+pub struct CloudPuff {
+    pub cloud: Entity,
+    pub offset: Vec3,
+    pub size: f32,
+}
+
+// Cloud deck a cloud belongs to. The low layer is scattered puffy clouds; the high one
+// is a sparse deck of thin, wide sheets well above them.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CloudLayer {
+    Low,
+    High,
+}
+
+impl CloudLayer {
+    // How much faster than its own pace the layer drifts with the wind
+    pub fn wind_factor(self) -> f32 {
+        match self {
+            CloudLayer::Low => 1.0,
+            CloudLayer::High => HIGH_CLOUD_WIND_FACTOR,
+        }
+    }
+}
+
+// Dark blob projected on the water under a cloud
+#[derive(Component)]
+pub struct CloudShadow {
+    pub cloud: Entity,
 }
 
 #[derive(Component)]
 pub struct Water;
 
+// Fading patch of disturbed water left behind the plane on the surface
+#[derive(Component)]
+pub struct Wake {
+    pub lifetime: Timer,
+}
+
+// Ring spreading out over the water from a touchdown or from the plane sailing
+#[derive(Component)]
+pub struct Ripple {
+    pub lifetime: Timer,
+    pub end_size: f32, // Radius the ring has grown to when it fades out
+}
+
+// Effect components
+#[derive(Component)]
+pub struct Particle {
+    pub velocity: Vec3,
+    pub lifetime: Timer,
+    pub start_size: f32,
+    pub end_size: f32,
+    pub gravity: f32, // Downward acceleration, for heavy particles like spray
+}
+
+// A bird in a flock; flies along `heading`, weaving by its `phase`
+#[derive(Component)]
+pub struct Bird {
+    pub heading: f32,
+    pub phase: f32,
+}
+
+// A tracer round fired from the plane's gun
+#[derive(Component)]
+pub struct Projectile {
+    pub velocity: Vec3,
+    pub lifetime: Timer,
+}
+
+// Particle belonging to the vapor trail, counted to cap the trail length
+#[derive(Component)]
+pub struct Contrail;
+
 // UI components
 #[derive(Component)]
 pub struct FlightDataText;
 
 #[derive(Component)]
 pub struct ControlsText;
+
+#[derive(Component)]
+pub struct RecordsText;
+
+#[derive(Component)]
+pub struct LandingBanner;
+
+// Full-screen overlay darkening (blackout) or reddening (redout) the view under sustained G
+#[derive(Component)]
+pub struct GForceVignette;
+
+#[derive(Component)]
+pub struct LandingBannerText;
+
+#[derive(Component)]
+pub struct AirspeedText;
+
+#[derive(Component)]
+pub struct AltimeterText;
+
+#[derive(Component)]
+pub struct HeadingText;
+
+// Velocity vector marker on the HUD
+#[derive(Component)]
+pub struct FlightPathMarker;
+
+// Nose direction marker on the HUD
+#[derive(Component)]
+pub struct BoresightMarker;
+
+#[derive(Component)]
+pub struct DebugOverlayPanel;
+
+#[derive(Component)]
+pub struct DebugOverlayText;
+
+#[derive(Component)]
+pub struct PausePanel;
+
+// Everything spawned for the start menu, including its 2D camera, so it can be torn down
+#[derive(Component)]
+pub struct MainMenuItem;
+
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub enum MainMenuButton {
+    Start,
+    Quit,
+}
+
+#[derive(Component)]
+pub struct QuitDialogPanel;
+
+#[derive(Component)]
+pub struct QuitDialogText;
+
+// Audio components
+#[derive(Component)]
+pub struct EngineSound;
+
+#[derive(Component)]
+pub struct WindSound;
+
+// Minimap components
+#[derive(Component)]
+pub struct MinimapPanel;
+
+#[derive(Component)]
+pub struct MinimapPlaneMarker;
+
+// Dot on the minimap standing in for an island
+#[derive(Component)]
+pub struct MinimapIslandDot {
+    pub island: Entity,
+}
@@ -0,0 +1,100 @@
+use bevy::prelude::*;
+use std::path::PathBuf;
+use crate::resources::{FlightConfig, PlaneState, Fuel};
+
+pub const FLIGHT_CONFIG_FILE: &str = "config/flight.ron";
+
+// Where to look for the flight config: next to the executable first, then the
+// working directory so `cargo run` picks up the one in the repository
+fn flight_config_paths() -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    if let Some(exe_dir) = std::env::current_exe().ok().and_then(|exe| exe.parent().map(PathBuf::from)) {
+        paths.push(exe_dir.join(FLIGHT_CONFIG_FILE));
+    }
+    paths.push(PathBuf::from(FLIGHT_CONFIG_FILE));
+    paths
+}
+
+// Replace the default flight tuning with config/flight.ron when there is one.
+// A file that can't be read or parsed is reported and the defaults are kept.
+pub fn load_flight_config(
+    mut flight_config: ResMut<FlightConfig>,
+    mut plane_state: ResMut<PlaneState>,
+    mut fuel: ResMut<Fuel>,
+) {
+    let Some(path) = flight_config_paths().into_iter().find(|path| path.is_file()) else {
+        return;
+    };
+
+    let loaded = std::fs::read_to_string(&path)
+        .map_err(|err| err.to_string())
+        .and_then(|contents| ron::from_str::<FlightConfig>(&contents).map_err(|err| err.to_string()));
+
+    match loaded {
+        Ok(config) => {
+            info!("Loaded flight config from {}", path.display());
+            *flight_config = config;
+
+            // The plane launches at the configured minimum speed
+            plane_state.speed = flight_config.min_airspeed;
+            plane_state.momentum = Vec3::new(0.0, 0.0, -flight_config.min_airspeed);
+            fuel.amount = flight_config.fuel_capacity;
+        }
+        Err(err) => warn!("Ignoring {}: {}", path.display(), err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    const EXAMPLE: &str = include_str!("../config/flight.example.ron");
+
+    // Field names of a RON struct
+    fn field_names(ron_text: &str) -> BTreeSet<String> {
+        let ron::Value::Map(map) = ron::from_str::<ron::Value>(ron_text).unwrap() else {
+            panic!("not a RON struct");
+        };
+        map.keys()
+            .map(|key| match key {
+                ron::Value::String(name) => name.clone(),
+                other => panic!("unexpected key {:?}", other),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn example_config_parses() {
+        ron::from_str::<FlightConfig>(EXAMPLE).unwrap();
+    }
+
+    #[test]
+    fn example_config_lists_every_field() {
+        let defaults = ron::to_string(&FlightConfig::default()).unwrap();
+        assert_eq!(field_names(EXAMPLE), field_names(&defaults));
+    }
+
+    #[test]
+    fn config_survives_a_round_trip() {
+        let config = FlightConfig {
+            max_airspeed: 95.5,
+            stall_speed: 17.25,
+            keyboard_smoothing: false,
+            fog_color: [0.1, 0.2, 0.3],
+            ..default()
+        };
+        let saved = ron::ser::to_string_pretty(&config, ron::ser::PrettyConfig::default()).unwrap();
+        assert_eq!(ron::from_str::<FlightConfig>(&saved).unwrap(), config);
+    }
+
+    #[test]
+    fn missing_fields_keep_their_defaults() {
+        let config = ron::from_str::<FlightConfig>("(max_airspeed: 95.0)").unwrap();
+        let defaults = FlightConfig::default();
+        assert_eq!(config.max_airspeed, 95.0);
+        assert_eq!(config.min_airspeed, defaults.min_airspeed);
+        assert_eq!(config.stall_speed, defaults.stall_speed);
+        assert_eq!(config.water_damping, defaults.water_damping);
+    }
+}
@@ -1,33 +1,347 @@
+use bevy::math::Vec3;
+
 // Game settings
 pub const MIN_AIRSPEED: f32 = 25.0;
 pub const MAX_AIRSPEED: f32 = 80.0;
 pub const ACCELERATION: f32 = 10.0;
-pub const WATER_SIZE: f32 = 1500.0;
-pub const ISLAND_COUNT: usize = 18;
-pub const CLOUD_COUNT: usize = 160;
+pub const WATER_SIZE: f32 = 1500.0; // Default world size; --world-size picks another
+pub const MIN_WORLD_SIZE: f32 = 500.0; // Smallest world that still fits the spawn point and game mode targets
+pub const CHUNK_SIZE: f32 = 500.0; // Side length of an endless ocean chunk
+pub const CHUNK_LOAD_RADIUS: i32 = 2; // Chunks this many cells from the plane's are populated
+pub const CHUNK_UNLOAD_RADIUS: i32 = 3; // Chunks further than this are cleared; the gap stops edge flicker
+pub const GAME_RNG_STREAM: u64 = 0x6A09_E667_F3BC_C908; // Mixed into the seed so the runtime randomness differs from the world's
+pub const MAX_ALTITUDE: f32 = 1000.0;
+pub const ISLAND_COUNT: usize = 18; // In the default-sized world; other sizes scale it with the area
+pub const CLOUD_COUNT: usize = 160; // Likewise, before the graphics quality thins them out
 pub const PLANE_SCALE: f32 = 2.0;
+pub const DEBUG_PRINT_INTERVAL: f32 = 1.0; // Seconds between flight debug log lines
+pub const ZERO_G_RESTORE_TIME: f32 = 3.0; // Seconds gravity takes to ease back in after zero-G
+
+// Damage constants
+pub const MAX_HEALTH: f32 = 100.0;
+pub const SMOKE_HEALTH_FRACTION: f32 = 0.7; // Start smoking below this fraction of health
+pub const FIRE_HEALTH_FRACTION: f32 = 0.25; // Flames below this fraction of health
+pub const SMOKE_MAX_RATE: f32 = 40.0; // Smoke particles per second at zero health
+pub const FIRE_RATE: f32 = 25.0; // Fire particles per second when critically damaged
+pub const ISLAND_IMPACT_MIN_SPEED: f32 = 8.0; // Hitting an island slower than this does no damage
+pub const ISLAND_DAMAGE_PER_SPEED: f32 = 2.5; // Health lost per unit of impact speed above the minimum
+pub const ISLAND_GRID_RESOLUTION: usize = 33; // Terrain samples per side of an island's mesh and heightfield
+pub const ISLAND_LOW_DETAIL_RESOLUTION: usize = 9; // Terrain samples per side of the distant-island mesh
+pub const ISLAND_DETAIL_DISTANCE: f32 = 300.0; // Islands farther than this from the plane use the low-detail mesh
+pub const ISLAND_DRAW_DISTANCE: f32 = 1000.0; // Islands farther than this from the plane are hidden
+pub const CLOUD_DRAW_DISTANCE: f32 = 700.0; // Clouds (and their shadows) farther than this from the plane are hidden
+pub const ISLAND_BEACH_START: f32 = 0.8; // Fraction of the island radius where the hill gives way to beach
+pub const ISLAND_BEACH_HEIGHT: f32 = 0.6; // Height of the beach's inner edge, unscaled
+pub const ISLAND_SHELF_WIDTH: f32 = 0.3; // How far past the beach (as a fraction of the radius) the shelf slopes down
+pub const ISLAND_SHELF_DEPTH: f32 = 3.0; // Depth the shelf reaches below the water, unscaled
+pub const ISLAND_MAX_PALMS: usize = 4; // Palm trees per island, at least one except on rocky islands
+pub const ISLAND_MAX_ROCKS: usize = 3; // Decorative rocks per island, possibly none
+
+// Propeller wash constants
+pub const PROP_WASH_MAX_ALTITUDE: f32 = 8.0; // No spray above this altitude
+pub const PROP_WASH_RATE: f32 = 60.0; // Spray particles per second at full throttle on the water
+
+// Audio constants
+pub const ENGINE_MIN_PITCH: f32 = 0.8; // Engine playback speed at minimum airspeed
+pub const ENGINE_MAX_PITCH: f32 = 1.6; // ...and at maximum airspeed
+pub const ENGINE_MIN_VOLUME: f32 = 0.4;
+pub const ENGINE_MAX_VOLUME: f32 = 0.9;
+pub const ENGINE_IDLE_PITCH: f32 = 0.5; // Idling while sailing on the water
+pub const ENGINE_IDLE_VOLUME: f32 = 0.25;
+pub const WIND_MAX_VOLUME: f32 = 0.6; // Wind volume at maximum airspeed
+pub const SPLASH_FULL_VOLUME_SPEED: f32 = 15.0; // Impact speed at which the splash plays at full volume
+pub const SPLASH_MIN_VOLUME: f32 = 0.3;
+
+// Spray constants
+pub const SPRAY_GRAVITY: f32 = 9.81; // Water droplets fall back down
+pub const SPLASH_PARTICLES_PER_SPEED: f32 = 6.0; // Splash particles per unit of impact speed
+pub const SPLASH_MAX_PARTICLES: usize = 120;
+
+// Contrail constants
+pub const CONTRAIL_MIN_ALTITUDE: f32 = 150.0; // No vapor trail below this altitude
+pub const CONTRAIL_MIN_SPEED: f32 = 55.0; // ...or below this airspeed
+pub const CONTRAIL_RATE: f32 = 30.0; // Trail particles per second
+pub const CONTRAIL_LIFETIME: f32 = 3.0;
+pub const CONTRAIL_MAX_PARTICLES: usize = 120;
+
+// Wake constants
+pub const WAKE_MIN_SPEED: f32 = 2.0; // Horizontal speed on the water needed to leave a wake
+pub const WAKE_SPAWN_INTERVAL: f32 = 0.1; // Seconds between wake patches, which caps how many exist
+pub const WAKE_LIFETIME: f32 = 2.0;
+pub const WAKE_HEIGHT: f32 = 0.04; // Above the wave surface under the patch, below cloud shadows
+pub const WAKE_START_SIZE: f32 = 2.0;
+pub const WAKE_END_SIZE: f32 = 6.0;
+pub const WAKE_MAX_ALPHA: f32 = 0.5;
+
+// Ripple constants
+pub const RIPPLE_HEIGHT: f32 = 0.05; // Above the wave surface under the ring, just above the wake
+pub const RIPPLE_THICKNESS: f32 = 0.08; // Width of the ring as a fraction of its radius
+pub const RIPPLE_LIFETIME: f32 = 2.5;
+pub const RIPPLE_START_SIZE: f32 = 1.0;
+pub const RIPPLE_MAX_ALPHA: f32 = 0.6;
+pub const RIPPLE_SAILING_INTERVAL: f32 = 0.8; // Seconds between ripples while the plane is on the water
+pub const RIPPLE_SAILING_SIZE: f32 = 5.0;
+pub const RIPPLE_SIZE_PER_IMPACT_SPEED: f32 = 2.5; // Final radius per m/s of touchdown sink rate
+pub const RIPPLE_MAX_SIZE: f32 = 25.0;
+
+// Day/night constants
+pub const DAY_LENGTH: f32 = 600.0; // Real seconds for a full 24 hour day
+pub const START_HOUR: f32 = 10.0;
+pub const TIME_FAST_FORWARD: f32 = 60.0; // Clock speed-up while F5 is held
+pub const SUN_DAY_ILLUMINANCE: f32 = 50000.0;
+pub const MOON_ILLUMINANCE: f32 = 800.0;
+pub const FILL_DAY_ILLUMINANCE: f32 = 15000.0;
+pub const DAY_AMBIENT_BRIGHTNESS: f32 = 0.5;
+pub const NIGHT_AMBIENT_BRIGHTNESS: f32 = 0.05;
+
+// Wind constants
+pub const WIND_BASE_HEADING: f32 = 1.107; // Radians from +Z toward +X the wind blows on average, roughly (1, 0, 0.5)
+pub const WIND_HEADING_SWING: f32 = 0.6; // How far the wind direction wanders either side of the base
+pub const WIND_MEAN_STRENGTH: f32 = 6.0; // Average wind speed at altitude
+pub const WIND_STRENGTH_SWING: f32 = 0.5; // Gusts and lulls, as a fraction of the mean
+pub const WIND_FULL_ALTITUDE: f32 = 60.0; // Wind builds up from nothing at the water to full strength here
+
+// Thermal constants
+pub const THERMAL_RADIUS_PER_SCALE: f32 = 30.0; // Thermal radius per unit of island scale
+pub const THERMAL_STRENGTH_PER_SCALE: f32 = 5.0; // Upward acceleration at the center per unit of island scale
+pub const THERMAL_TOP_ALTITUDE: f32 = 300.0; // Thermals stop lifting above this altitude
+pub const THERMAL_FADE_HEIGHT: f32 = 50.0; // Height below the top over which the lift fades out
+pub const TURBULENCE_WATER_HEIGHT: f32 = 12.0; // Air gets bumpy below this height over the water, more so lower down
+pub const TURBULENCE_CLOUD_REACH: f32 = 1.5; // Cloud turbulence reaches this many half-sizes from a cloud's center
+pub const TURBULENCE_ANGULAR_JITTER: f32 = 0.25; // Largest random angular velocity (radians per second) at full strength
+pub const TURBULENCE_MOMENTUM_JITTER: f32 = 6.0; // Largest random acceleration at full strength
+pub const THERMAL_DEBUG_PARTICLE_RATE: f32 = 4.0; // Marker particles per second per thermal with the debug overlay on
+
+// Bird constants
+pub const BIRD_FLOCK_COUNT: usize = 6;
+pub const BIRDS_PER_FLOCK: usize = 7;
+pub const BIRD_FLOCK_SPREAD: f32 = 8.0; // How far birds start from the middle of their flock
+pub const BIRD_MIN_ALTITUDE: f32 = 15.0;
+pub const BIRD_MAX_ALTITUDE: f32 = 60.0;
+pub const BIRD_SPEED: f32 = 8.0;
+pub const BIRD_WEAVE: f32 = 0.8; // Radians either side of its course a flock wanders
+pub const BIRD_FLAP_RATE: f32 = 12.0;
+pub const BIRD_HIT_RADIUS: f32 = 3.0; // Plane-to-bird distance that counts as a strike
+pub const BIRD_HIT_SPEED_FACTOR: f32 = 0.9; // Airspeed kept after hitting a bird
+pub const BIRD_FEATHER_COUNT: usize = 12;
+
+// Cloud constants
+pub const HIGH_CLOUD_SHARE: f64 = 0.25; // Fraction of the clouds in the high, thin layer; the rest are low puffs
+pub const HIGH_CLOUD_WIND_FACTOR: f32 = 1.8; // The high layer drifts this much faster in the stronger wind aloft
+pub const GOLDEN_ANGLE: f32 = 2.399_963; // Turn between successive points of an evenly filled sunflower spiral
+pub const CLOUD_PUFF_TEXTURE_SIZE: u32 = 64; // Pixels across the soft round billboard cloud texture
+pub const CLOUD_PUFFS_LOW: usize = 7; // Billboards making up a low cloud
+pub const CLOUD_PUFFS_HIGH: usize = 12; // The wide high sheets need more to fill them out
+pub const CLOUD_EDGE_FADE_DISTANCE: f32 = 100.0; // Clouds shrink away over this distance from the world edge
+
+// Cloud shadow constants
+pub const CLOUD_SHADOW_HEIGHT: f32 = 0.05; // Just above the water surface
+pub const CLOUD_SHADOW_MAX_ALPHA: f32 = 0.35;
+pub const CLOUD_SHADOW_FADE_ALTITUDE: f32 = 150.0; // Clouds at this altitude cast no visible shadow
+pub const CLOUD_SHADOW_RESTYLE_STEP: f32 = 0.02; // Change in sun shadow strength before the shadows are faded to it
 
 // Flight physics constants
 pub const TURN_SPEED: f32 = 0.5;
 pub const PITCH_SENSITIVITY: f32 = 0.8;
 pub const BASE_ROLL_SENSITIVITY: f32 = 0.2;
 pub const YAW_SENSITIVITY: f32 = 0.3;
-pub const MOMENTUM: f32 = 0.98;
-pub const TURN_MOMENTUM: f32 = 0.99;
+pub const PHYSICS_TIMESTEP_HZ: f64 = 60.0; // Fixed steps per second of the flight integration
+pub const TUNING_FRAME_RATE: f32 = 60.0; // Frame rate the per-frame factors (water damping, ...) were tuned at
+// Time constants of the momentum smoothing, in seconds: momentum covers about 63% of the way
+// to its target in this time (and 95% in three times it). Matches the old per-frame blend
+// factors of 0.98 and 0.99 at 60 fps.
+pub const MOMENTUM_RESPONSE_TIME: f32 = 0.83;
+pub const TURN_RESPONSE_TIME: f32 = 1.66;
 pub const AUTO_LEVEL_SPEED: f32 = 0.9;
 pub const BANK_TURN_RATIO: f32 = 0.5;
+pub const CONTROL_REFERENCE_SPEED: f32 = 55.0; // Airspeed at which the controls reach full authority
+pub const MIN_CONTROL_AUTHORITY: f32 = 0.3; // Control response never drops below this fraction
+pub const CLIMB_SPEED_LOSS: f32 = 9.0; // Airspeed traded per second per unit of climb (regained in a dive)
+pub const STALL_SPEED: f32 = 20.0; // Forward airspeed below which the wings stop lifting
+pub const STALL_RECOVERY_FACTOR: f32 = 1.2; // Stall clears above STALL_SPEED times this
+pub const STALL_PITCH_RATE: f32 = 1.2; // Nose-down rotation while stalled, radians per second
+pub const GROUND_EFFECT_MIN_HEIGHT: f32 = 0.5; // Below this the plane is on the water, not in ground effect
+pub const GROUND_EFFECT_MAX_HEIGHT: f32 = 8.0; // Ground effect fades out at this height above the water
+pub const GROUND_EFFECT_LIFT: f32 = 3.0; // Extra upward acceleration at the lowest height and full airspeed
+pub const LIFT_COEFFICIENT: f32 = 0.06; // Lift balances standard gravity at about 40 in level flight
+pub const WING_INCIDENCE: f32 = 0.1; // Radians of angle of attack the wing has in level flight
+
+// Propeller constants
+pub const PROPELLER_SPIN_PER_SPEED: f32 = 1.0; // Radians per second of spin per unit of airspeed
+pub const PROPELLER_IDLE_SPIN: f32 = 4.0; // Slowest spin, when idling on the water
+
+// Weapon constants
+pub const FIRE_COOLDOWN: f32 = 0.12; // Seconds between shots while fire is held
+pub const MUZZLE_SPEED: f32 = 150.0; // Projectile speed on top of the plane's own
+pub const PROJECTILE_LIFETIME: f32 = 2.0;
+pub const PROJECTILE_RADIUS: f32 = 0.15;
+pub const PROJECTILE_HIT_RADIUS: f32 = 5.0; // Distance from an enemy plane's center that counts as a hit
+
+// Input constants
+pub const GAMEPAD_DEAD_ZONE: f32 = 0.15; // Stick and trigger values below this are ignored
+pub const TRIM_RATE: f32 = 0.4; // Trim change per second while a trim key is held
+pub const TRIM_LIMIT: f32 = 0.6; // Most pitch input the trim can hold
+pub const BOOST_SPEED_FACTOR: f32 = 1.4; // Top speed while boosting, as a multiple of the max airspeed
+pub const BOOST_ACCELERATION_FACTOR: f32 = 3.0; // Acceleration while boosting, as a multiple of the normal rate
+pub const BOOST_DRAIN_RATE: f32 = 0.25; // Boost meter used per second (a full meter lasts 4 s)
+pub const BOOST_RECHARGE_RATE: f32 = 0.1; // Boost meter regained per second while not boosting
+pub const BOOST_EASE_RATE: f32 = 8.0; // Airspeed lost per second while easing back to the top speed after a boost
+pub const BOOST_BLOOM_INTENSITY: f32 = 0.4; // Camera bloom while boosting
+pub const BLOOM_INTENSITY: f32 = 0.15; // Camera bloom the rest of the time
+pub const FOG_COLOR: [f32; 3] = [0.6, 0.8, 1.0]; // Daytime haze, a paler take on the sky blue
+pub const SKY_DOME_RADIUS: f32 = 1900.0; // Gradient sky sphere around the camera, just inside its 2000 far plane
+pub const SKY_GRADIENT_HEIGHT: f32 = 0.5; // Height on the unit sky sphere where the zenith color is fully in
+pub const FOG_VISIBILITY: f32 = 1500.0; // Haze hides things by about here, inside the camera's 2000 far plane
+pub const MOTION_BLUR_MAX_SHUTTER_ANGLE: f32 = 0.6; // Motion blur strength at the boosted top speed (none at the minimum airspeed)
+pub const MOTION_BLUR_SAMPLES: u32 = 2; // Motion blur samples taken each way per pixel
+pub const BOOST_BLOOM_RESPONSE_TIME: f32 = 0.25; // Seconds for the bloom to ease ~63% of the way between the two
+pub const AUTOPILOT_ALTITUDE_GAIN: f32 = 0.02; // Climb angle (as the sine) asked for per meter off the held altitude
+pub const AUTOPILOT_MAX_CLIMB: f32 = 0.2; // Steepest climb or descent the autopilot flies, as the sine of the angle
+pub const AUTOPILOT_PITCH_GAIN: f32 = 4.0; // Pitch input per unit of climb angle error
+pub const MOUSE_SENSITIVITY: f32 = 0.004; // Virtual stick deflection per pixel of mouse movement
+pub const MOUSE_DEAD_ZONE: f32 = 0.05; // Mouse stick deflection below this is ignored
+pub const MOUSE_RECENTER_RATE: f32 = 2.0; // How quickly the mouse stick drifts back to center
+pub const MOUSE_YAW_MIX: f32 = 0.3; // Yaw added with mouse roll, as a fraction of it
+pub const KEYBOARD_RAMP_TIME: f32 = 0.25; // Seconds for a smoothed key axis to go from center to full deflection
+
+// Auto-throttle constants
+pub const AUTO_THROTTLE_GAIN: f32 = 1.5; // Extra power per unit of airspeed error
+pub const AUTO_THROTTLE_CLIMB_GAIN: f32 = 0.6; // Extra power in a climb, as a fraction of the setpoint
+pub const AUTO_THROTTLE_SETPOINT_RATE: f32 = 15.0; // Setpoint change per second while a throttle key is held
+
+// Fuel constants
+pub const FUEL_CAPACITY: f32 = 100.0;
+pub const FUEL_BURN_RATE: f32 = 0.5; // Fuel per second at full throttle, on top of the idle burn
+pub const FUEL_IDLE_BURN: f32 = 0.05; // Fuel per second with the engine idling
+pub const FUEL_OUT_SPEED_DECAY: f32 = 2.0; // Airspeed lost per second once the engine cuts out
+pub const REFUEL_RATE: f32 = 5.0; // Fuel per second while on the water near an island
+pub const REFUEL_RADIUS: f32 = 60.0; // How close to an island's center the plane must be to refuel
+
+// Play area boundary constants
+pub const BOUNDARY_MARGIN: f32 = 150.0; // Width of the band inside the play area edge where the plane is turned back
+pub const BOUNDARY_TURN_RATE: f32 = 0.6; // Yaw rate (radians per second) steering the plane back at the edge; grows past it
+pub const BOUNDARY_RESET_DISTANCE: f32 = 300.0; // How far past the edge the plane can fly before it's reset to the start
 
 // Water physics constants
 pub const WATER_DAMPING: f32 = 0.8; // Stronger damping for more realistic water resistance
-pub const WATER_ROTATION_DAMPING: f32 = 0.6; // Stronger rotation damping in water
 pub const WATER_LEVEL_SPEED: f32 = 15.3; // Much faster auto-leveling on water
 pub const TAKEOFF_SPEED_THRESHOLD: f32 = 0.7; // Percentage of MAX_AIRSPEED needed for takeoff
 pub const TAKEOFF_FORCE: f32 = 2.0;
+pub const TAKEOFF_MIN_PITCH: f32 = 0.1; // Radians of nose up needed for the takeoff push
 pub const WATER_IMPACT_THRESHOLD: f32 = 4.0; // Lower threshold for bounce effect
 pub const WATER_BOUNCE_FACTOR: f32 = 0.4; // Stronger bounce on impact
 pub const WATER_IMPACT_SLOWDOWN: f32 = 0.6; // Stronger slowdown on impact
 pub const WATER_STOP_SPEED: f32 = 0.95; // How quickly the plane slows to a stop on water
-pub const WATER_STOP_THRESHOLD: f32 = 5.0; // Speed below which the plane will come to a complete stop
+pub const WATER_STOP_THRESHOLD: f32 = 5.0; // Speed below which a landing plane coasts to a stop
 pub const WATER_STABILIZE_FACTOR: f32 = 0.9; // Reduces twitching by stabilizing movement
-pub const WATER_SAILING_SPEED: f32 = 5.0; // Speed for sailing on water
+pub const WATER_SAILING_DRAG: f32 = 0.95; // Extra per-frame slowdown while coasting below the stop threshold
+pub const WATER_STOPPED_SPEED: f32 = 0.5; // Coasting slower than this comes to a stop
 pub const WATER_LEVEL_ROTATION_SPEED: f32 = 10.5; // How quickly the plane levels to horizontal
+pub const WATER_SURFACE_THICKNESS: f32 = 0.1; // Height of the surface-only water sensor
+pub const WATER_VOLUME_DEPTH: f32 = 20.0; // Depth of the water sensor with --water-volume
+pub const WATER_BUOYANCY: f32 = 6.0; // Upward acceleration per unit of depth when submerged
+pub const WATER_VERTICAL_DRAG: f32 = 1.5; // How strongly water resists vertical motion when submerged
+pub const PLANE_HULL_DEPTH: f32 = 0.25; // Hull bottom below the plane's origin (half the collider's height)
+pub const WATER_FLOAT_BUOYANCY: f32 = 65.0; // Upward acceleration per unit of hull below the surface; floats about 0.15 deep
+pub const WATER_FLOAT_DAMPING: f32 = 5.0; // Damping of the bobbing while floating
+pub const WATER_MAX_SAFE_DEPTH: f32 = 12.0; // Diving deeper than this counts as a crash
+pub const WAVE_AMPLITUDE: f32 = 0.3; // Furthest the animated water surface moves from rest
+pub const WATER_SUBDIVISIONS: u32 = 127; // Grid resolution of the water mesh when waves are on
+
+// Menu constants
+pub const MENU_REPEAT_DELAY: f32 = 0.4; // Seconds a direction is held before it starts repeating
+pub const MENU_REPEAT_INTERVAL: f32 = 0.12; // Seconds between repeats while held
+pub const FORCE_QUIT_HOLD_TIME: f32 = 1.5; // Seconds to hold the exit key to quit without confirming
+
+// Camera constants
+// The plane flies along its local -Z axis, so the nose is on the negative side
+pub const COCKPIT_CAMERA_OFFSET: Vec3 = Vec3::new(0.0, 0.5, -1.5); // Plane-local eye point
+pub const ORBIT_CAMERA_DISTANCE: f32 = 30.0;
+pub const CHASE_CAMERA_DISTANCE: f32 = 25.0; // How far behind the plane the chase camera sits
+pub const CHASE_CAMERA_HEIGHT: f32 = 8.0; // How far above the plane it sits at that distance
+pub const CHASE_CAMERA_BANK_OFFSET: f32 = 5.0; // Sideways swing at full bank at that distance
+pub const CHASE_CAMERA_RESPONSE_TIME: f32 = 0.15; // Seconds for the chase camera to close ~63% of the gap to its spot
+pub const CHASE_CAMERA_ROTATION_RESPONSE_TIME: f32 = 0.17; // Seconds for it to turn ~63% of the way to look ahead of the plane
+pub const CHASE_CAMERA_BANK_TILT: f32 = 0.25; // Share of the plane's bank the chase camera's horizon follows
+pub const CHASE_CAMERA_MIN_ZOOM: f32 = 10.0; // Closest the mouse wheel brings the chase camera
+pub const CHASE_CAMERA_MAX_ZOOM: f32 = 80.0; // Furthest the mouse wheel takes it
+pub const CHASE_CAMERA_ZOOM_STEP: f32 = 0.1; // Fraction of the distance changed per wheel notch
+pub const CAMERA_MIN_DISTANCE: f32 = 4.0; // Closest an island can push the chase camera to the plane
+pub const CAMERA_COLLISION_MARGIN: f32 = 1.0; // Gap kept between the chase camera and an island in the way
+pub const CAMERA_WATER_CLEARANCE: f32 = 1.0; // Height the chase camera stays above the water surface
+pub const ORBIT_CAMERA_RESPONSE_TIME: f32 = 0.125; // Seconds for the orbit camera to close ~63% of the gap to its spot
+pub const ORBIT_MOUSE_SENSITIVITY: f32 = 0.005; // Radians of orbit per pixel of mouse movement
+pub const ORBIT_MIN_PITCH: f32 = -0.3; // Limits keep the camera from flipping over the poles
+pub const ORBIT_MAX_PITCH: f32 = 1.4;
+pub const SPECTATOR_SPEED: f32 = 40.0; // Free camera speed in units per second
+pub const SPECTATOR_FAST_FACTOR: f32 = 4.0; // Speed multiplier while Shift is held
+pub const SPECTATOR_MOUSE_SENSITIVITY: f32 = 0.003; // Radians of turn per pixel of mouse movement
+pub const SPECTATOR_MAX_PITCH: f32 = 1.5; // Stops short of straight up or down
+pub const RECORDER_MAX_DURATION: f32 = 60.0; // Seconds of flight kept for replay
+pub const CAMERA_SHAKE_MAX: f32 = 1.5; // Largest camera shake offset from an impact
+pub const CAMERA_SHAKE_PER_SPEED: f32 = 0.08; // Shake offset per unit of impact speed
+pub const CAMERA_SHAKE_DECAY: f32 = 8.0; // Decay rate; a shake has died away after about half a second
+pub const CAMERA_FOV_MIN: f32 = 55.0 * std::f32::consts::PI / 180.0; // Field of view at (or below) the minimum airspeed
+pub const CAMERA_FOV_MAX: f32 = 68.0 * std::f32::consts::PI / 180.0; // Field of view at the max airspeed
+pub const CAMERA_FOV_BOOST: f32 = 78.0 * std::f32::consts::PI / 180.0; // Field of view at the boosted top speed
+pub const CAMERA_FOV_RESPONSE_TIME: f32 = 0.33; // Seconds for the field of view to close ~63% of the gap to its target
+
+// HUD constants
+pub const HUD_SMOOTHING_TIME: f32 = 0.15; // Time constant in seconds for easing HUD numbers
+pub const HUD_MARKER_DISTANCE: f32 = 1000.0; // How far ahead HUD markers are projected
+pub const FLIGHT_PATH_MARKER_SIZE: f32 = 20.0;
+pub const FLIGHT_PATH_MARKER_MIN_SPEED: f32 = 1.0; // Hide the marker when barely moving
+pub const BORESIGHT_MARKER_SIZE: f32 = 6.0;
+pub const LOW_ALTITUDE_WARNING: f32 = 5.0; // Altimeter turns red below this while airborne
+pub const LOW_AIRSPEED_MARGIN: f32 = 1.1; // Airspeed turns yellow below the minimum airspeed times this
+pub const STANDARD_GRAVITY: f32 = 9.81; // One G, so zero-G mode still reads the plane's own accelerations
+pub const G_FORCE_LIMIT: f32 = 12.0; // Measured G-force is clamped to this either way
+pub const G_FORCE_SMOOTHING_TIME: f32 = 0.15; // Time constant smoothing the measured G-force
+pub const BLACKOUT_G: f32 = 5.0; // Sustained positive G above this starts to black out the view
+pub const REDOUT_G: f32 = -2.0; // Sustained negative G below this starts to red out the view
+pub const G_STRAIN_RATE: f32 = 0.15; // Strain built up per second per G past the threshold
+pub const G_STRAIN_RECOVERY: f32 = 0.4; // Strain shed per second once the load eases
+pub const G_VIGNETTE_BORDER: f32 = 18.0; // Width of the darker vignette edge, in percent of the screen
+pub const LANDING_BANNER_TIME: f32 = 3.0; // Seconds the landing rating stays on screen
+
+// Landing rating constants. A touchdown loses points in proportion to each of these,
+// losing all of that part's share at the limit.
+pub const LANDING_MIN_AIRBORNE_TIME: f32 = 2.0; // Shorter hops off the water aren't rated as landings
+pub const LANDING_MAX_SINK_RATE: f32 = 8.0; // Vertical speed at touchdown
+pub const LANDING_MAX_BANK: f32 = 0.35; // Radians of roll at touchdown
+pub const LANDING_MAX_PITCH: f32 = 0.3; // Radians of nose up or down at touchdown
+pub const LANDING_SINK_WEIGHT: f32 = 60.0; // Share of the 100 points for the sink rate
+pub const LANDING_BANK_WEIGHT: f32 = 20.0;
+pub const LANDING_PITCH_WEIGHT: f32 = 20.0;
+pub const LANDING_SMOOTH_SCORE: f32 = 75.0; // Ratings from here up are smooth landings
+pub const LANDING_ROUGH_SCORE: f32 = 40.0; // Ratings below this are rough
+
+// Minimap constants
+pub const MINIMAP_SIZE: f32 = 200.0; // Width and height in pixels; covers the whole water surface
+pub const MINIMAP_ISLAND_DOT_SIZE: f32 = 6.0;
+pub const MINIMAP_PLANE_MARKER_WIDTH: f32 = 6.0;
+pub const MINIMAP_PLANE_MARKER_LENGTH: f32 = 14.0;
+
+// Game mode constants
+pub const LANDING_TARGET_DISTANCE: f32 = 400.0; // Distance ahead of the spawn point of the landing target
+pub const LANDING_ZONE_RADIUS: f32 = 60.0; // Touchdowns this close to the target's center keep their full rating
+pub const LANDING_ZONE_FALLOFF: f32 = 200.0; // Distance past the zone over which the score falls to nothing
+pub const ENEMY_COUNT: usize = 3;
+pub const ENEMY_SPAWN_DISTANCE: f32 = 400.0; // Distance from the spawn point enemies start at
+pub const ENEMY_SPAWN_ALTITUDE: f32 = 60.0;
+pub const ENEMY_SPEED: f32 = 45.0;
+pub const ENEMY_TURN_RATE: f32 = 0.8; // Radians per second
+pub const ENEMY_MAX_LEAD_TIME: f32 = 3.0; // Furthest ahead enemies aim at the player's path, in seconds
+pub const ENEMY_MIN_ALTITUDE: f32 = 20.0; // Enemies pull up below this
+pub const ENEMY_ISLAND_AVOID_DISTANCE: f32 = 40.0; // Enemies climb over islands within this distance
+pub const ENEMY_MAX_BANK: f32 = 0.7; // Radians of bank in the tightest turn
+pub const RING_COUNT: usize = 10;
+pub const RING_RADIUS: f32 = 12.0; // Fly within this distance of a ring's center to clear it
+pub const RING_THICKNESS: f32 = 1.5;
+pub const RING_SPACING: f32 = 150.0; // Distance between consecutive rings
+pub const RING_MAX_TURN: f32 = 0.6; // Largest change of heading between rings, in radians
+pub const RING_MIN_ALTITUDE: f32 = 20.0;
+pub const RING_MAX_ALTITUDE: f32 = 100.0;
+
+// Screenshot constants
+pub const SCREENSHOT_DIR: &str = "screenshots"; // Relative to the working directory
@@ -0,0 +1,157 @@
+use bevy::{
+    prelude::*,
+    pbr::{NotShadowCaster, NotShadowReceiver},
+    render::mesh::{SphereKind, SphereMeshBuilder},
+};
+use std::f32::consts::PI;
+use crate::components::{Sun, FillLight, FollowCamera, SkyDome};
+use crate::constants::*;
+use crate::resources::{FlightConfig, TimeOfDay};
+
+const DAY_SKY: Color = Color::srgb(0.5, 0.8, 1.0);
+const DUSK_SKY: Color = Color::srgb(0.9, 0.5, 0.3);
+const NIGHT_SKY: Color = Color::srgb(0.02, 0.03, 0.08);
+const DAY_ZENITH: Color = Color::srgb(0.15, 0.4, 0.85);
+const DUSK_ZENITH: Color = Color::srgb(0.25, 0.25, 0.55);
+const NIGHT_ZENITH: Color = Color::srgb(0.0, 0.0, 0.02);
+const DUSK_SUN: Color = Color::srgb(1.0, 0.6, 0.35);
+const MOON_LIGHT: Color = Color::srgb(0.6, 0.7, 1.0);
+
+// Advance the clock; holding F5 fast-forwards it
+pub fn advance_time_of_day(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+    mut time_of_day: ResMut<TimeOfDay>,
+) {
+    let speed = if keyboard_input.pressed(KeyCode::F5) { TIME_FAST_FORWARD } else { 1.0 };
+    let hours = time.delta_secs() * speed * 24.0 / time_of_day.day_length;
+    time_of_day.hour = (time_of_day.hour + hours).rem_euclid(24.0);
+}
+
+// Direction toward the sun: rises in the east (+X) at 6:00, highest at noon,
+// sets in the west at 18:00 and is below the horizon overnight
+fn sun_direction(hour: f32) -> Vec3 {
+    let angle = (hour - 6.0) / 12.0 * PI;
+    Vec3::new(angle.cos(), angle.sin(), 0.3).normalize()
+}
+
+// How dark the sun's shadows fall at an hour: none with the sun down, faint while
+// it's low and full once it's high
+pub fn sun_shadow_strength(hour: f32) -> f32 {
+    smoothstep(0.0, 0.35, sun_direction(hour).y)
+}
+
+// Gradient sky: a sphere around the camera in the zenith color, fading to transparent
+// toward the horizon through its vertex alpha so the clear color shows there. The clear
+// color is kept at the haze color, so the horizon blends into the fogged distance.
+pub fn spawn_sky_dome(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+) {
+    let mut mesh = SphereMeshBuilder::new(1.0, SphereKind::Uv { sectors: 32, stacks: 16 }).build();
+    let colors: Vec<[f32; 4]> = mesh
+        .attribute(Mesh::ATTRIBUTE_POSITION)
+        .and_then(|positions| positions.as_float3())
+        .unwrap_or_default()
+        .iter()
+        .map(|position| [1.0, 1.0, 1.0, smoothstep(0.0, SKY_GRADIENT_HEIGHT, position[1])])
+        .collect();
+    mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+
+    commands.spawn((
+        Mesh3d(meshes.add(mesh)),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color: DAY_ZENITH,
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            fog_enabled: false,
+            cull_mode: None, // Seen from inside
+            ..default()
+        })),
+        Transform::from_scale(Vec3::splat(SKY_DOME_RADIUS)),
+        NotShadowCaster,
+        NotShadowReceiver,
+        SkyDome,
+        Name::new("Sky Dome"),
+    ));
+}
+
+// Keep the sky centered on the camera so it always looks infinitely far away
+pub fn follow_sky_dome(
+    camera_query: Query<&Transform, (With<FollowCamera>, Without<SkyDome>)>,
+    mut sky_query: Query<&mut Transform, With<SkyDome>>,
+) {
+    let (Ok(camera_transform), Ok(mut sky_transform)) = (camera_query.get_single(), sky_query.get_single_mut()) else {
+        return;
+    };
+    sky_transform.translation = camera_transform.translation;
+}
+
+// Move the lights and recolor the sky and ambient light for the time of day.
+// The sun goes orange near the horizon; at night a dim bluish moon takes over.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn update_daylight(
+    time_of_day: Res<TimeOfDay>,
+    mut ambient_light: ResMut<AmbientLight>,
+    mut sun_query: Query<(&mut DirectionalLight, &mut Transform), (With<Sun>, Without<FillLight>)>,
+    mut fill_query: Query<&mut DirectionalLight, (With<FillLight>, Without<Sun>)>,
+    flight_config: Res<FlightConfig>,
+    mut camera_query: Query<(&mut Camera, Option<&mut DistanceFog>), With<FollowCamera>>,
+    sky_query: Query<&MeshMaterial3d<StandardMaterial>, With<SkyDome>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let sun_dir = sun_direction(time_of_day.hour);
+    let elevation = sun_dir.y;
+    let daylight = elevation.clamp(0.0, 1.0);
+
+    // 0 at night, 1 once the sun is up; then 0 at dusk, 1 once it's high
+    let dawn = smoothstep(-0.1, 0.05, elevation);
+    let day = smoothstep(0.05, 0.35, elevation);
+
+    if let Ok((mut sun, mut transform)) = sun_query.get_single_mut() {
+        let (light_dir, illuminance, color) = if elevation > 0.0 {
+            (sun_dir, SUN_DAY_ILLUMINANCE * daylight, DUSK_SUN.mix(&Color::WHITE, day))
+        } else {
+            (-sun_dir, MOON_ILLUMINANCE, MOON_LIGHT)
+        };
+        sun.illuminance = illuminance;
+        sun.color = color;
+        *transform = Transform::from_translation(light_dir * 100.0).looking_at(Vec3::ZERO, Vec3::Y);
+    }
+
+    if let Ok(mut fill) = fill_query.get_single_mut() {
+        fill.illuminance = FILL_DAY_ILLUMINANCE * daylight;
+    }
+
+    ambient_light.brightness = NIGHT_AMBIENT_BRIGHTNESS.lerp(DAY_AMBIENT_BRIGHTNESS, dawn);
+
+    // The haze takes on the dusk and night sky too
+    let [red, green, blue] = flight_config.fog_color;
+    let haze = NIGHT_SKY.mix(&DUSK_SKY, dawn).mix(&Color::srgb(red, green, blue), day);
+
+    // With the gradient sky the clear color is its horizon, which matches the haze
+    let sky_material = sky_query
+        .get_single()
+        .ok()
+        .and_then(|material| materials.get_mut(&material.0));
+    let horizon = match sky_material {
+        Some(material) => {
+            material.base_color = NIGHT_ZENITH.mix(&DUSK_ZENITH, dawn).mix(&DAY_ZENITH, day);
+            haze
+        }
+        None => NIGHT_SKY.mix(&DUSK_SKY, dawn).mix(&DAY_SKY, day),
+    };
+
+    if let Ok((mut camera, fog)) = camera_query.get_single_mut() {
+        camera.clear_color = ClearColorConfig::Custom(horizon);
+        if let Some(mut fog) = fog {
+            fog.color = haze;
+        }
+    }
+}
+
+fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
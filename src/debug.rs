@@ -0,0 +1,119 @@
+use bevy::prelude::*;
+use avian3d::prelude::*;
+use crate::components::{Plane, Water, DebugOverlayPanel, DebugOverlayText};
+use crate::resources::{PlaneState, FlightConfig, DebugOverlay, DebugPrintTimer, ZeroG};
+use crate::constants::ZERO_G_RESTORE_TIME;
+use crate::plane_systems::takeoff_ready;
+
+// F4 switches gravity off so the plane flies purely on thrust and momentum.
+// Switching back eases the previous gravity in with restore_gravity.
+pub fn toggle_zero_g(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut gravity: ResMut<Gravity>,
+    mut zero_g: ResMut<ZeroG>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::F4) {
+        return;
+    }
+
+    zero_g.enabled = !zero_g.enabled;
+    if zero_g.enabled {
+        // Still easing back in from the last time, so keep the gravity it's heading for
+        zero_g.normal.get_or_insert(gravity.0);
+        gravity.0 = Vec3::ZERO;
+        info!("Zero-G on");
+    } else {
+        info!("Zero-G off");
+    }
+}
+
+// Ease gravity back in over ZERO_G_RESTORE_TIME after zero-G. The wings only take the
+// weight once the plane starts to sink into the air under them, so full gravity at once
+// would drop it before the lift caught up.
+pub fn restore_gravity(
+    time: Res<Time>,
+    mut gravity: ResMut<Gravity>,
+    mut zero_g: ResMut<ZeroG>,
+) {
+    let (false, Some(normal)) = (zero_g.enabled, zero_g.normal) else {
+        return;
+    };
+    let step = normal.length() * time.delta_secs() / ZERO_G_RESTORE_TIME;
+    gravity.0 = gravity.0.move_towards(normal, step);
+    if gravity.0 == normal {
+        zero_g.normal = None;
+    }
+}
+
+// F3 shows or hides the debug overlay and turns the flight debug log on or off with it
+pub fn toggle_debug_overlay(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut debug_overlay: ResMut<DebugOverlay>,
+    mut debug_timer: ResMut<DebugPrintTimer>,
+    mut panel_query: Query<&mut Node, With<DebugOverlayPanel>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::F3) {
+        return;
+    }
+
+    debug_overlay.enabled = !debug_overlay.enabled;
+    debug_timer.0.reset();
+    for mut node in panel_query.iter_mut() {
+        node.display = if debug_overlay.enabled { Display::Flex } else { Display::None };
+    }
+}
+
+// Fill in the debug overlay every frame and log the same line once per DEBUG_PRINT_INTERVAL.
+// Nothing is computed while the overlay is off.
+#[allow(clippy::too_many_arguments)]
+pub fn update_debug_overlay(
+    debug_overlay: Res<DebugOverlay>,
+    mut debug_timer: ResMut<DebugPrintTimer>,
+    time: Res<Time>,
+    plane_state: Res<PlaneState>,
+    flight_config: Res<FlightConfig>,
+    plane_query: Query<(&Transform, &CollidingEntities), With<Plane>>,
+    water_query: Query<Entity, With<Water>>,
+    mut text_query: Query<&mut Text, With<DebugOverlayText>>,
+) {
+    if !debug_overlay.enabled {
+        return;
+    }
+    let Ok((transform, colliding_entities)) = plane_query.get_single() else {
+        return;
+    };
+    let is_on_water = water_query
+        .get_single()
+        .is_ok_and(|water_entity| colliding_entities.contains(&water_entity));
+
+    let info = debug_info(&plane_state, &flight_config, transform, is_on_water);
+
+    if debug_timer.0.tick(time.delta()).just_finished() {
+        debug!("{}", info);
+    }
+    for mut text in text_query.iter_mut() {
+        text.0 = info.clone();
+    }
+}
+
+fn debug_info(
+    plane_state: &PlaneState,
+    flight_config: &FlightConfig,
+    transform: &Transform,
+    is_on_water: bool,
+) -> String {
+    let (pitch, _, _) = transform.rotation.to_euler(EulerRot::XYZ);
+
+    // Check takeoff conditions
+    let takeoff_ready = takeoff_ready(plane_state, flight_config, transform);
+
+    format!(
+        "Airspeed: {:.1} ({:.0}%), Altitude: {:.1}, Pitch: {:.1}°, Status: {}, Takeoff Ready: {}",
+        plane_state.speed,
+        (plane_state.speed / flight_config.max_airspeed) * 100.0,
+        transform.translation.y,
+        pitch.to_degrees(),
+        if is_on_water { "ON WATER" } else { "AIRBORNE" },
+        if takeoff_ready { "YES" } else { "NO" }
+    )
+}
@@ -0,0 +1,101 @@
+use bevy::prelude::*;
+use crate::components::{EnemyPlane, Island, Plane};
+use crate::constants::*;
+use crate::resources::{PlaneState, WorldConfig};
+use crate::setup::spawn_plane;
+
+// Put a few enemy planes in a ring around the spawn point, facing the player
+pub fn spawn_enemies(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+) {
+    let enemy_material = materials.add(StandardMaterial {
+        base_color: Color::srgb(0.2, 0.25, 0.2),
+        perceptual_roughness: 0.4,
+        metallic: 0.6,
+        ..default()
+    });
+
+    for i in 0..ENEMY_COUNT {
+        let angle = i as f32 / ENEMY_COUNT as f32 * std::f32::consts::TAU;
+        let position = Vec3::new(angle.sin(), 0.0, angle.cos()) * ENEMY_SPAWN_DISTANCE
+            + Vec3::Y * ENEMY_SPAWN_ALTITUDE;
+        let transform = Transform::from_translation(position)
+            .looking_at(Vec3::Y * ENEMY_SPAWN_ALTITUDE, Vec3::Y)
+            .with_scale(Vec3::splat(PLANE_SCALE));
+
+        let enemy = spawn_plane(commands, meshes, materials, transform, enemy_material.clone());
+        commands.entity(enemy).insert((
+            EnemyPlane {
+                speed: ENEMY_SPEED,
+                turn_rate: ENEMY_TURN_RATE,
+            },
+            Name::new("Enemy plane"),
+        ));
+    }
+}
+
+// Chase the player. Each enemy aims where the player will be by the time it gets
+// there, pulls up over islands, turns back from the edge of the world, and turns
+// toward all that no faster than its turn rate.
+pub fn enemy_ai(
+    time: Res<Time>,
+    plane_state: Res<PlaneState>,
+    world_config: Res<WorldConfig>,
+    plane_query: Query<&Transform, (With<Plane>, Without<EnemyPlane>)>,
+    island_query: Query<&Transform, (With<Island>, Without<EnemyPlane>)>,
+    mut enemy_query: Query<(&mut Transform, &EnemyPlane)>,
+) {
+    let Ok(player_transform) = plane_query.get_single() else {
+        return;
+    };
+    let dt = time.delta_secs();
+    let player_position = player_transform.translation;
+
+    for (mut transform, enemy) in enemy_query.iter_mut() {
+        let position = transform.translation;
+
+        // Lead the target by the time it would take to fly to it
+        let time_to_target = position.distance(player_position) / enemy.speed;
+        let intercept = player_position + plane_state.momentum * time_to_target.min(ENEMY_MAX_LEAD_TIME);
+        let mut desired = (intercept - position).normalize_or_zero();
+
+        // Climb away from any island close below
+        let near_island = island_query.iter().any(|island| {
+            island.translation.with_y(0.0).distance(position.with_y(0.0)) < ENEMY_ISLAND_AVOID_DISTANCE
+        });
+        if near_island && position.y < ENEMY_MIN_ALTITUDE * 2.0 {
+            desired += Vec3::Y;
+        }
+
+        // Stay clear of the water, the ceiling and the edge of the play area
+        if position.y < ENEMY_MIN_ALTITUDE {
+            desired += Vec3::Y;
+        } else if position.y > world_config.max_altitude * 0.8 {
+            desired -= Vec3::Y;
+        }
+        let edge = world_config.play_area_half_extent * 0.9;
+        if position.x.abs() > edge || position.z.abs() > edge {
+            desired += -position.with_y(0.0).normalize_or_zero();
+        }
+
+        let forward = *transform.forward();
+        let desired = desired.try_normalize().unwrap_or(forward);
+
+        // Turn toward the desired heading, limited by the turn rate
+        let angle = forward.angle_between(desired);
+        let max_turn = enemy.turn_rate * dt;
+        let new_forward = if angle > max_turn {
+            Quat::IDENTITY.slerp(Quat::from_rotation_arc(forward, desired), max_turn / angle) * forward
+        } else {
+            desired
+        };
+
+        // Bank into the turn
+        let turn_side = forward.cross(new_forward).y.signum() * (angle / max_turn.max(1e-4)).min(1.0);
+        let up = Quat::from_axis_angle(new_forward, -turn_side * ENEMY_MAX_BANK) * Vec3::Y;
+        transform.look_to(new_forward, up);
+        transform.translation += new_forward * enemy.speed * dt;
+    }
+}
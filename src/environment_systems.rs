@@ -1,14 +1,329 @@
-use bevy::prelude::*;
-use crate::components::{Plane, FollowCamera, Cloud};
-use crate::constants::WATER_SIZE;
+use bevy::{prelude::*, pbr::NotShadowCaster, input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel}, window::{CursorGrabMode, PrimaryWindow}};
+use avian3d::prelude::*;
+use std::f32::consts::PI;
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use crate::components::{Plane, GhostPlane, FollowCamera, SpectatorCamera, Cloud, CloudPuff, CloudShadow, Island, IslandLod, Water, Wake, Ripple};
+use crate::constants::{
+    CLOUD_SHADOW_MAX_ALPHA, CLOUD_SHADOW_FADE_ALTITUDE, CLOUD_SHADOW_RESTYLE_STEP, COCKPIT_CAMERA_OFFSET,
+    ORBIT_CAMERA_DISTANCE, ORBIT_MOUSE_SENSITIVITY, ORBIT_MIN_PITCH, ORBIT_MAX_PITCH,
+    ORBIT_CAMERA_RESPONSE_TIME, SPECTATOR_SPEED, SPECTATOR_FAST_FACTOR, SPECTATOR_MOUSE_SENSITIVITY,
+    SPECTATOR_MAX_PITCH, CAMERA_SHAKE_DECAY, CLOUD_EDGE_FADE_DISTANCE, WAKE_MIN_SPEED,
+    WIND_BASE_HEADING, WIND_HEADING_SWING, WIND_MEAN_STRENGTH, WIND_STRENGTH_SWING,
+    WAKE_SPAWN_INTERVAL, WAKE_LIFETIME, WAKE_HEIGHT, WAKE_START_SIZE, WAKE_END_SIZE, WAKE_MAX_ALPHA,
+    RIPPLE_HEIGHT, RIPPLE_THICKNESS, RIPPLE_LIFETIME, RIPPLE_START_SIZE, RIPPLE_MAX_ALPHA,
+    RIPPLE_SAILING_INTERVAL, RIPPLE_SAILING_SIZE, RIPPLE_SIZE_PER_IMPACT_SPEED, RIPPLE_MAX_SIZE,
+    CAMERA_FOV_MIN, CAMERA_FOV_MAX, CAMERA_FOV_BOOST, CAMERA_FOV_RESPONSE_TIME, BOOST_SPEED_FACTOR,
+    CAMERA_COLLISION_MARGIN, CAMERA_WATER_CLEARANCE, CHASE_CAMERA_BANK_OFFSET, CHASE_CAMERA_ZOOM_STEP,
+};
+use crate::waves::water_surface_height;
+use crate::events::WaterImpact;
+use crate::util::smoothing;
+use crate::resources::{WorldConfig, ViewPreset, CameraMode, OrbitCamera, CameraSettings, CameraShake, Wind, MouseFlight, PlaneState, FlightConfig, TimeOfDay, GameRng, WaterState};
+use crate::chunks::{ChunkManager, stream_chunks, recenter_water};
+use crate::day_night::{advance_time_of_day, update_daylight, follow_sky_dome, sun_shadow_strength};
+use crate::plane_systems::{auto_throttle, reset_input};
+use crate::schedule::GameSet;
+use crate::states::GameState;
+use crate::waves::animate_waves;
 
+// The world around the plane and the cameras watching it: wind, sky, water, clouds,
+// streamed chunks and level of detail, and the camera modes.
+//
+// Ordering: the camera controls read input in GameSet::Input, ahead of the fixed steps,
+// with the spectator toggle ahead of the flight controls since it can engage the
+// autopilot. The wind is updated in GameSet::Physics before the plane's physics step
+// uses it. The world updates run chained at the start of GameSet::PostPhysics, and the
+// cameras in GameSet::Camera, once the plane has moved.
+pub struct EnvironmentPlugin;
+
+impl Plugin for EnvironmentPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ViewPreset>()
+            .init_resource::<CameraMode>()
+            .init_resource::<OrbitCamera>()
+            .init_resource::<CameraSettings>()
+            .init_resource::<CameraShake>()
+            .init_resource::<TimeOfDay>()
+            .init_resource::<Wind>()
+            .init_resource::<ChunkManager>()
+            .add_systems(RunFixedMainLoop, (
+                toggle_spectator.before(reset_input),
+                (view_presets, cycle_camera_mode, free_look, zoom_camera).chain(),
+            ).run_if(in_state(GameState::Playing)).in_set(GameSet::Input))
+            .add_systems(FixedUpdate, update_wind.before(auto_throttle).in_set(GameSet::Physics))
+            .add_systems(Update, (
+                (advance_time_of_day, update_daylight).chain(),
+                (recenter_water, animate_waves).chain(),
+                cloud_movement,
+                cloud_shadows,
+                (stream_chunks, lod_system).chain(),
+                (water_wake, update_wakes, water_ripples, update_ripples).chain(),
+            ).chain().in_set(GameSet::PostPhysics))
+            .add_systems(Update, (
+                spectator_camera,
+                camera_follow,
+                apply_camera_shake,
+                dynamic_fov,
+                follow_sky_dome,
+                billboard_clouds,
+            ).chain().in_set(GameSet::Camera));
+    }
+}
+
+// Numpad views: camera offset relative to the plane's heading (x right, y up, z behind).
+// Only the plane's yaw is used so the views stay steady while it rolls and pitches.
+pub const VIEW_PRESETS: [(KeyCode, Vec3); 9] = [
+    (KeyCode::Numpad1, Vec3::new(-18.0, 6.0, 18.0)), // Rear left
+    (KeyCode::Numpad2, Vec3::new(0.0, 6.0, 25.0)), // Rear
+    (KeyCode::Numpad3, Vec3::new(18.0, 6.0, 18.0)), // Rear right
+    (KeyCode::Numpad4, Vec3::new(-25.0, 3.0, 0.0)), // Left
+    (KeyCode::Numpad5, Vec3::new(0.0, 40.0, 0.0)), // Top
+    (KeyCode::Numpad6, Vec3::new(25.0, 3.0, 0.0)), // Right
+    (KeyCode::Numpad7, Vec3::new(-18.0, 6.0, -18.0)), // Front left
+    (KeyCode::Numpad8, Vec3::new(0.0, 3.0, -25.0)), // Front
+    (KeyCode::Numpad9, Vec3::new(18.0, 6.0, -18.0)), // Front right
+];
+
+// Numpad keys latch a preset view; pressing it again or Numpad0 returns to the chase camera
+pub fn view_presets(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut view_preset: ResMut<ViewPreset>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Numpad0) {
+        view_preset.0 = None;
+        return;
+    }
+
+    for (index, (key, _)) in VIEW_PRESETS.iter().enumerate() {
+        if keyboard_input.just_pressed(*key) {
+            view_preset.0 = if view_preset.0 == Some(index) { None } else { Some(index) };
+        }
+    }
+}
+
+// C cycles chase, cockpit and orbit cameras. Also drops any latched numpad view
+// so the new mode is visible straight away.
+pub fn cycle_camera_mode(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut camera_mode: ResMut<CameraMode>,
+    mut view_preset: ResMut<ViewPreset>,
+) {
+    if keyboard_input.just_pressed(KeyCode::KeyC) {
+        *camera_mode = camera_mode.next();
+        view_preset.0 = None;
+        info!("Camera mode: {:?}", *camera_mode);
+    }
+}
+
+// The mouse wheel pulls the chase camera back for the scenery or in for precision
+pub fn zoom_camera(
+    mut mouse_wheel: EventReader<MouseWheel>,
+    mut camera_settings: ResMut<CameraSettings>,
+) {
+    // Pixel scrolling (touchpads) comes in much finer steps than wheel notches
+    let notches: f32 = mouse_wheel
+        .read()
+        .map(|wheel| match wheel.unit {
+            MouseScrollUnit::Line => wheel.y,
+            MouseScrollUnit::Pixel => wheel.y / 100.0,
+        })
+        .sum();
+    if notches == 0.0 {
+        return;
+    }
+
+    let distance = camera_settings.distance * (1.0 - notches * CHASE_CAMERA_ZOOM_STEP);
+    camera_settings.distance = distance.clamp(camera_settings.min_zoom, camera_settings.max_zoom);
+}
+
+// Holding V switches to the orbit camera for a look around, returning to the
+// previous mode on release. In orbit mode the mouse swings the camera around the plane.
+pub fn free_look(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut mouse_motion: EventReader<MouseMotion>,
+    mut camera_mode: ResMut<CameraMode>,
+    mut view_preset: ResMut<ViewPreset>,
+    mut orbit: ResMut<OrbitCamera>,
+    mouse_flight: Res<MouseFlight>,
+    mut mode_before_free_look: Local<Option<CameraMode>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::KeyV) && *camera_mode != CameraMode::Orbit {
+        *mode_before_free_look = Some(*camera_mode);
+        *camera_mode = CameraMode::Orbit;
+        view_preset.0 = None;
+    }
+    if keyboard_input.just_released(KeyCode::KeyV)
+        && let Some(previous) = mode_before_free_look.take()
+    {
+        *camera_mode = previous;
+    }
+
+    // With mouse flight on, the mouse only orbits the camera while V is held
+    let delta: Vec2 = mouse_motion.read().map(|motion| motion.delta).sum();
+    let mouse_for_camera = !mouse_flight.enabled || keyboard_input.pressed(KeyCode::KeyV);
+    if *camera_mode != CameraMode::Orbit || delta == Vec2::ZERO || !mouse_for_camera {
+        return;
+    }
+
+    orbit.yaw -= delta.x * ORBIT_MOUSE_SENSITIVITY;
+    orbit.pitch = (orbit.pitch + delta.y * ORBIT_MOUSE_SENSITIVITY).clamp(ORBIT_MIN_PITCH, ORBIT_MAX_PITCH);
+}
+
+// F6 frees the camera from the plane to fly around the world, and returns it on a
+// second press. The plane flies on hands-off meanwhile, with the autopilot engaged
+// so it holds its altitude.
+pub fn toggle_spectator(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut plane_state: ResMut<PlaneState>,
+    mouse_flight: Res<MouseFlight>,
+    mut plane_query: Query<(&Transform, &mut Visibility), With<Plane>>,
+    camera_query: Query<(Entity, &Transform, Has<SpectatorCamera>), With<FollowCamera>>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::F6) {
+        return;
+    }
+    let Ok((camera_entity, camera_transform, spectating)) = camera_query.get_single() else {
+        return;
+    };
+
+    if spectating {
+        commands.entity(camera_entity).remove::<SpectatorCamera>();
+        info!("Spectator camera off");
+    } else {
+        let (yaw, pitch, _) = camera_transform.rotation.to_euler(EulerRot::YXZ);
+        commands.entity(camera_entity).insert(SpectatorCamera { yaw, pitch });
+        info!("Spectator camera on");
+
+        if let Ok((plane_transform, mut plane_visibility)) = plane_query.get_single_mut() {
+            // The cockpit view hides the plane, which should be seen from outside
+            plane_visibility.set_if_neq(Visibility::Inherited);
+            if !plane_state.autopilot && !plane_state.crashed {
+                plane_state.autopilot = true;
+                plane_state.autopilot_altitude = plane_transform.translation.y;
+                info!("Autopilot engaged");
+            }
+        }
+    }
+
+    // Mouse look needs the cursor captured; afterwards it goes back to how mouse flight wants it
+    let capture = !spectating || mouse_flight.enabled;
+    for mut window in window_query.iter_mut() {
+        window.cursor_options.grab_mode = if capture { CursorGrabMode::Locked } else { CursorGrabMode::None };
+        window.cursor_options.visible = !capture;
+    }
+}
+
+// Fly the free camera: WASD moves along the view, E and Q rise and sink, the mouse
+// looks around, and holding Shift moves faster
+pub fn spectator_camera(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut mouse_motion: EventReader<MouseMotion>,
+    time: Res<Time>,
+    mut camera_query: Query<(&mut Transform, &mut SpectatorCamera)>,
+) {
+    let delta: Vec2 = mouse_motion.read().map(|motion| motion.delta).sum();
+    let Ok((mut camera_transform, mut spectator)) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    spectator.yaw -= delta.x * SPECTATOR_MOUSE_SENSITIVITY;
+    spectator.pitch = (spectator.pitch - delta.y * SPECTATOR_MOUSE_SENSITIVITY)
+        .clamp(-SPECTATOR_MAX_PITCH, SPECTATOR_MAX_PITCH);
+    camera_transform.rotation = Quat::from_euler(EulerRot::YXZ, spectator.yaw, spectator.pitch, 0.0);
+
+    let axis = |negative, positive| {
+        keyboard_input.pressed(positive) as i32 as f32 - keyboard_input.pressed(negative) as i32 as f32
+    };
+    let direction = camera_transform.forward() * axis(KeyCode::KeyS, KeyCode::KeyW)
+        + camera_transform.right() * axis(KeyCode::KeyA, KeyCode::KeyD)
+        + Vec3::Y * axis(KeyCode::KeyQ, KeyCode::KeyE);
+    let fast = keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let speed = if fast { SPECTATOR_SPEED * SPECTATOR_FAST_FACTOR } else { SPECTATOR_SPEED };
+    camera_transform.translation += direction.normalize_or_zero() * speed * time.delta_secs();
+}
+
+// Chase, cockpit, orbit, or a latched numpad view of the plane, or of the ghost
+// plane during a replay. Yields to the spectator camera.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn camera_follow(
-    plane_query: Query<&Transform, With<Plane>>,
-    mut camera_query: Query<&mut Transform, (With<FollowCamera>, Without<Plane>)>,
+    mut plane_query: Query<(&Transform, &mut Visibility), With<Plane>>,
+    ghost_query: Query<&Transform, (With<GhostPlane>, Without<Plane>, Without<FollowCamera>)>,
+    mut camera_query: Query<&mut Transform, (With<FollowCamera>, Without<Plane>, Without<SpectatorCamera>)>,
+    view_preset: Res<ViewPreset>,
+    camera_mode: Res<CameraMode>,
+    orbit: Res<OrbitCamera>,
+    camera_shake: Res<CameraShake>,
+    camera_settings: Res<CameraSettings>,
+    world_config: Res<WorldConfig>,
+    spatial_query: SpatialQuery,
+    island_query: Query<(), With<Island>>,
     time: Res<Time>,
 ) {
-    let plane_transform = plane_query.single();
-    let mut camera_transform = camera_query.single_mut();
+    let (Ok((plane_transform, mut plane_visibility)), Ok(mut camera_transform)) =
+        (plane_query.get_single_mut(), camera_query.get_single_mut())
+    else {
+        return;
+    };
+
+    // Follow from where the camera would be without last frame's shake, so the
+    // smoothing never chases the jitter
+    camera_transform.translation -= camera_shake.offset;
+
+    let preset = view_preset.0.and_then(|index| VIEW_PRESETS.get(index));
+
+    // Hide the plane from the inside so it doesn't block the view
+    let replaying = ghost_query.get_single().ok();
+    let in_cockpit = preset.is_none() && *camera_mode == CameraMode::Cockpit && replaying.is_none();
+    let target_visibility = if in_cockpit { Visibility::Hidden } else { Visibility::Inherited };
+    plane_visibility.set_if_neq(target_visibility);
+    let plane_transform = replaying.unwrap_or(plane_transform);
+
+    if let Some((_, offset)) = preset {
+        let flat_forward = Vec3::from(plane_transform.forward())
+            .with_y(0.0)
+            .try_normalize()
+            .unwrap_or(Vec3::NEG_Z);
+        let heading = Transform::default().looking_to(flat_forward, Vec3::Y).rotation;
+        camera_transform.translation = plane_transform.translation + heading * *offset;
+
+        // Looking straight down needs the heading as its up vector
+        let up = if offset.with_y(0.0).length_squared() < 1.0 { flat_forward } else { Vec3::Y };
+        camera_transform.look_at(plane_transform.translation, up);
+        return;
+    }
+
+    match *camera_mode {
+        CameraMode::Chase => {}
+        CameraMode::Cockpit => {
+            // Ride along with the plane, rolling with it so the horizon tilts
+            camera_transform.translation = plane_transform.transform_point(COCKPIT_CAMERA_OFFSET);
+            camera_transform.rotation = plane_transform.rotation;
+            return;
+        }
+        CameraMode::Orbit => {
+            // Yaw is measured from straight behind the plane's heading
+            let flat_forward = Vec3::from(plane_transform.forward())
+                .with_y(0.0)
+                .try_normalize()
+                .unwrap_or(Vec3::NEG_Z);
+            let heading = Transform::default().looking_to(flat_forward, Vec3::Y).rotation;
+            let offset = Vec3::new(
+                orbit.yaw.sin() * orbit.pitch.cos(),
+                orbit.pitch.sin(),
+                orbit.yaw.cos() * orbit.pitch.cos(),
+            ) * ORBIT_CAMERA_DISTANCE;
+
+            // Ease into place so switching to and from the orbit doesn't snap
+            let alpha = smoothing(ORBIT_CAMERA_RESPONSE_TIME, time.delta_secs());
+            camera_transform.translation = camera_transform
+                .translation
+                .lerp(plane_transform.translation + heading * offset, alpha);
+            camera_transform.look_at(plane_transform.translation, Vec3::Y);
+            return;
+        }
+    }
     
     let back_dir = plane_transform.back();
     let back = Vec3::from(back_dir);
@@ -20,17 +335,40 @@ pub fn camera_follow(
     };
     
     let bank_angle = plane_transform.rotation.to_euler(EulerRot::ZYX).2;
-    let up_offset = Vec3::new(bank_angle.sin() * 5.0, 8.0, 0.0);
-    let back_offset = back_safe * 25.0;
+    let zoom_scale = camera_settings.zoom_scale();
+    let up_offset = Vec3::new(bank_angle.sin() * CHASE_CAMERA_BANK_OFFSET, camera_settings.height, 0.0) * zoom_scale;
+    let back_offset = back_safe * camera_settings.distance;
     let desired_position = plane_transform.translation + back_offset + up_offset;
     
-    let camera_smoothing = 3.0;
-    let alpha = 1.0 - (-time.delta_secs() * camera_smoothing).exp();
-    camera_transform.translation = camera_transform.translation.lerp(
-        desired_position,
-        alpha.clamp(0.0, 0.15)
-    );
-    
+    // Ease in with a time constant, so the lag behind the plane is the same at any frame rate
+    let alpha = smoothing(camera_settings.position_response_time, time.delta_secs());
+    camera_transform.translation = camera_transform.translation.lerp(desired_position, alpha);
+
+    // Keep the view clear on low passes: pull the camera in front of any island between it
+    // and the plane, and keep it above the waves. The smoothing above eases it back out.
+    let to_camera = camera_transform.translation - plane_transform.translation;
+    if let Ok(direction) = Dir3::new(to_camera) {
+        let island_hit = spatial_query.cast_ray_predicate(
+            plane_transform.translation,
+            direction,
+            to_camera.length(),
+            true,
+            &SpatialQueryFilter::default(),
+            &|entity| island_query.contains(entity),
+        );
+        if let Some(hit) = island_hit {
+            let distance = (hit.distance - CAMERA_COLLISION_MARGIN).max(camera_settings.min_distance);
+            camera_transform.translation = plane_transform.translation + direction * distance;
+        }
+    }
+    let water_floor = water_surface_height(
+        &world_config,
+        camera_transform.translation.x,
+        camera_transform.translation.z,
+        time.elapsed_secs(),
+    ) + CAMERA_WATER_CLEARANCE;
+    camera_transform.translation.y = camera_transform.translation.y.max(water_floor);
+
     let forward_dir = plane_transform.forward();
     let forward = Vec3::from(forward_dir);
     
@@ -40,25 +378,455 @@ pub fn camera_follow(
         forward
     };
     
+    // Turn toward the view smoothly rather than snapping to it, so the view doesn't run
+    // ahead of the lagging position in hard banks. The horizon tilts a little with the
+    // plane; looking almost straight up or down the up vector is kept from the current
+    // view so it never lines up with the view direction.
     let look_target = plane_transform.translation + forward_safe * 5.0;
-    camera_transform.look_at(look_target, Vec3::Y);
+    let Ok(look_direction) = Dir3::new(look_target - camera_transform.translation) else {
+        return;
+    };
+    let tilted_up = Vec3::Y.lerp(*plane_transform.up(), camera_settings.bank_tilt);
+    let up = if look_direction.cross(tilted_up).length_squared() > 0.01 {
+        tilted_up
+    } else {
+        *camera_transform.up()
+    };
+    let target_rotation = Transform::default().looking_to(look_direction, up).rotation;
+    let rotation_alpha = smoothing(camera_settings.rotation_response_time, time.delta_secs());
+    camera_transform.rotation = camera_transform.rotation.slerp(target_rotation, rotation_alpha);
+}
+
+// Jolt the camera by a random offset that dies away after an impact.
+// Runs after camera_follow, which takes the previous offset back off.
+pub fn apply_camera_shake(
+    mut camera_shake: ResMut<CameraShake>,
+    mut game_rng: ResMut<GameRng>,
+    mut camera_query: Query<&mut Transform, (With<FollowCamera>, Without<SpectatorCamera>)>,
+    time: Res<Time>,
+) {
+    let Ok(mut camera_transform) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    camera_shake.magnitude *= (-time.delta_secs() * CAMERA_SHAKE_DECAY).exp();
+    if camera_shake.magnitude < 0.01 {
+        camera_shake.magnitude = 0.0;
+    }
+
+    let rng = game_rng.rng();
+    camera_shake.offset = Vec3::new(
+        rng.gen_range(-1.0..1.0),
+        rng.gen_range(-1.0..1.0),
+        rng.gen_range(-1.0..1.0),
+    ) * camera_shake.magnitude;
+    camera_transform.translation += camera_shake.offset;
+}
+
+// Widen the field of view with airspeed for a sense of speed, further still past the
+// normal top speed while boosting, and ease it there so it never jumps
+pub fn dynamic_fov(
+    plane_state: Res<PlaneState>,
+    flight_config: Res<FlightConfig>,
+    time: Res<Time>,
+    mut camera_query: Query<&mut Projection, With<FollowCamera>>,
+) {
+    let Ok(mut projection) = camera_query.get_single_mut() else {
+        return;
+    };
+    let Projection::Perspective(perspective) = projection.as_mut() else {
+        return;
+    };
+
+    let speed = plane_state.speed;
+    let target = if speed <= flight_config.max_airspeed {
+        let t = (speed - flight_config.min_airspeed) / (flight_config.max_airspeed - flight_config.min_airspeed);
+        CAMERA_FOV_MIN.lerp(CAMERA_FOV_MAX, t.clamp(0.0, 1.0))
+    } else {
+        let boost_cap = flight_config.max_airspeed * BOOST_SPEED_FACTOR;
+        let t = (speed - flight_config.max_airspeed) / (boost_cap - flight_config.max_airspeed);
+        CAMERA_FOV_MAX.lerp(CAMERA_FOV_BOOST, t.clamp(0.0, 1.0))
+    };
+
+    let alpha = smoothing(CAMERA_FOV_RESPONSE_TIME, time.delta_secs());
+    perspective.fov = perspective.fov.lerp(target, alpha).clamp(CAMERA_FOV_MIN, CAMERA_FOV_BOOST);
+}
+
+// Let the wind wander in direction and strength. Sines at unrelated slow rates
+// give a drift that never visibly repeats.
+pub fn update_wind(time: Res<Time>, mut wind: ResMut<Wind>) {
+    let t = time.elapsed_secs();
+
+    let heading = WIND_BASE_HEADING
+        + WIND_HEADING_SWING * (0.7 * (t * 0.013).sin() + 0.3 * (t * 0.037).sin());
+    let gust = 0.6 * (t * 0.021).sin() + 0.4 * (t * 0.089).sin();
+
+    wind.direction = Vec3::new(heading.sin(), 0.0, heading.cos());
+    wind.strength = WIND_MEAN_STRENGTH * (1.0 + WIND_STRENGTH_SWING * gust);
 }
 
 pub fn cloud_movement(
     time: Res<Time>,
-    mut cloud_query: Query<(&mut Transform, &Cloud)>,
+    world_config: Res<WorldConfig>,
+    wind: Res<Wind>,
+    mut game_rng: ResMut<GameRng>,
+    mut cloud_query: Query<(Entity, &mut Transform, &Cloud)>,
 ) {
     let dt = time.delta_secs();
-    
-    for (mut transform, cloud) in cloud_query.iter_mut() {
-        let wind_direction = Vec3::new(1.0, 0.0, 0.5).normalize();
-        transform.translation += wind_direction * cloud.speed * dt;
-        
-        if transform.translation.x > WATER_SIZE / 2.0 {
-            transform.translation.x = -WATER_SIZE / 2.0;
+    let wrap_bound = world_config.spawn_radius;
+
+    // Clouds keep their own pace but speed up and slow down with the wind
+    let wind_drift = wind.direction * (wind.strength / WIND_MEAN_STRENGTH);
+    let drift = |cloud: &Cloud| wind_drift * cloud.speed * cloud.layer.wind_factor() * dt;
+
+    // One draw from the seeded generator a frame, mixed with each cloud's id, gives every
+    // wrapping cloud its own scatter. It comes out the same every run whatever order the
+    // clouds are moved in, so they can move in parallel.
+    let frame_seed = if world_config.endless { 0 } else { game_rng.rng().gen_range(0..=u64::MAX) };
+
+    cloud_query.par_iter_mut().for_each(|(entity, mut transform, cloud)| {
+        transform.translation += drift(cloud);
+
+        // In the endless ocean clouds drift off with their chunk instead
+        if world_config.endless {
+            return;
+        }
+
+        // Wrap each axis to the opposite edge, scattering the other axis so
+        // wrapped clouds don't re-enter in lines
+        let wrap_x = wrap_coordinate(transform.translation.x, wrap_bound);
+        let wrap_z = wrap_coordinate(transform.translation.z, wrap_bound);
+        if wrap_x.is_some() || wrap_z.is_some() {
+            let mut rng = StdRng::seed_from_u64(frame_seed ^ entity.to_bits());
+            let scatter = Vec2::new(
+                rng.gen_range(-wrap_bound..wrap_bound),
+                rng.gen_range(-wrap_bound..wrap_bound),
+            );
+            if let Some(wrapped) = wrap_x {
+                transform.translation.x = wrapped;
+                transform.translation.z = scatter.y;
+            }
+            if let Some(wrapped) = wrap_z {
+                transform.translation.z = wrapped;
+                transform.translation.x = scatter.x;
+            }
+        }
+
+        // Shrink clouds away as they approach the edge so the wrap isn't visible
+        let edge_distance = wrap_bound
+            - transform.translation.x.abs().max(transform.translation.z.abs());
+        let fade = (edge_distance / CLOUD_EDGE_FADE_DISTANCE).clamp(0.0, 1.0);
+        transform.scale = cloud.size * (fade * fade * (3.0 - 2.0 * fade)).max(0.01);
+    });
+}
+
+// Wrapped position of a coordinate that has left [-bound, bound], or None if it's still inside
+pub fn wrap_coordinate(value: f32, bound: f32) -> Option<f32> {
+    if (-bound..=bound).contains(&value) {
+        return None;
+    }
+    Some((value + bound).rem_euclid(2.0 * bound) - bound)
+}
+
+// Higher clouds cast fainter shadows
+pub fn cloud_shadow_alpha(altitude: f32) -> f32 {
+    CLOUD_SHADOW_MAX_ALPHA * (1.0 - altitude / CLOUD_SHADOW_FADE_ALTITUDE).clamp(0.0, 1.0)
+}
+
+// ...and blurrier ones, which we fake by spreading the blob wider
+fn cloud_shadow_spread(altitude: f32) -> f32 {
+    1.0 + (altitude / CLOUD_SHADOW_FADE_ALTITUDE).clamp(0.0, 1.0)
+}
+
+// Shadows fade with the sun. Their materials are only rewritten once the strength has moved
+// on by a step, or for new shadows, so they aren't sent to the GPU again every frame.
+pub fn cloud_shadows(
+    time_of_day: Res<TimeOfDay>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut applied_strength: Local<Option<f32>>,
+    cloud_query: Query<&Transform, (With<Cloud>, Without<CloudShadow>)>,
+    mut shadow_query: Query<(&mut Transform, Ref<CloudShadow>, &MeshMaterial3d<StandardMaterial>)>,
+) {
+    let strength = sun_shadow_strength(time_of_day.hour);
+    // Settling at full or none always restyles, so the shadows don't stop a step short
+    let restyle_all = applied_strength.is_none_or(|applied| {
+        (applied - strength).abs() >= CLOUD_SHADOW_RESTYLE_STEP || (applied != strength && (strength == 0.0 || strength == 1.0))
+    });
+    if restyle_all {
+        *applied_strength = Some(strength);
+    }
+    let strength = applied_strength.unwrap_or(strength);
+
+    for (mut shadow_transform, shadow, material) in shadow_query.iter_mut() {
+        let Ok(cloud_transform) = cloud_query.get(shadow.cloud) else {
+            continue;
+        };
+
+        if (restyle_all || shadow.is_added())
+            && let Some(material) = materials.get_mut(&material.0)
+        {
+            material.base_color.set_alpha(cloud_shadow_alpha(cloud_transform.translation.y) * strength);
+        }
+
+        // The shadow mesh lies flat, so its local Y axis spans the world Z axis
+        let spread = cloud_shadow_spread(cloud_transform.translation.y);
+        shadow_transform.translation.x = cloud_transform.translation.x;
+        shadow_transform.translation.z = cloud_transform.translation.z;
+        shadow_transform.scale = Vec3::new(
+            cloud_transform.scale.x * spread,
+            cloud_transform.scale.z * spread,
+            1.0,
+        );
+    }
+}
+
+// Turn each billboard cloud puff to face the camera, carrying it along with its cloud.
+// A puff is as wide as its share of the cloud's horizontal size and squashed toward the
+// cloud's height, so flat clouds stay flat. Puffs show and hide with their cloud.
+#[allow(clippy::type_complexity)]
+pub fn billboard_clouds(
+    camera_query: Query<&Transform, (With<FollowCamera>, Without<CloudPuff>)>,
+    cloud_query: Query<(&Transform, &Visibility), (With<Cloud>, Without<CloudPuff>)>,
+    mut puff_query: Query<(&mut Transform, &mut Visibility, &CloudPuff), Without<Cloud>>,
+) {
+    let Ok(camera_transform) = camera_query.get_single() else {
+        return;
+    };
+
+    puff_query.par_iter_mut().for_each(|(mut transform, mut visibility, puff)| {
+        let Ok((cloud_transform, cloud_visibility)) = cloud_query.get(puff.cloud) else {
+            return;
+        };
+        visibility.set_if_neq(*cloud_visibility);
+        if *cloud_visibility == Visibility::Hidden {
+            return;
         }
-        if transform.translation.z > WATER_SIZE / 2.0 {
-            transform.translation.z = -WATER_SIZE / 2.0;
+
+        let cloud_scale = cloud_transform.scale;
+        let width = puff.size * (cloud_scale.x + cloud_scale.z) / 2.0;
+        let height = (puff.size * cloud_scale.y * 2.0).min(width);
+        transform.translation = cloud_transform.translation + puff.offset * cloud_scale;
+        transform.rotation = camera_transform.rotation;
+        transform.scale = Vec3::new(width, height, 1.0);
+    });
+}
+
+// Distance-based level of detail: hide clouds (with their shadows) and islands far from
+// the plane, and draw islands past the detail distance with their low-detail mesh
+#[allow(clippy::type_complexity)]
+pub fn lod_system(
+    world_config: Res<WorldConfig>,
+    plane_query: Query<&Transform, With<Plane>>,
+    mut cloud_query: Query<(&Transform, &mut Visibility), (With<Cloud>, Without<Plane>)>,
+    mut shadow_query: Query<(&CloudShadow, &mut Visibility), Without<Cloud>>,
+    mut island_query: Query<(&Transform, &mut Visibility, &mut Mesh3d, Option<&IslandLod>), (With<Island>, Without<Cloud>, Without<CloudShadow>)>,
+) {
+    let Ok(plane_transform) = plane_query.get_single() else {
+        return;
+    };
+    let plane_position = plane_transform.translation;
+    let draw = |shown: bool| if shown { Visibility::Inherited } else { Visibility::Hidden };
+
+    for (transform, mut visibility) in cloud_query.iter_mut() {
+        let shown = transform.translation.distance(plane_position) < world_config.cloud_draw_distance;
+        visibility.set_if_neq(draw(shown));
+    }
+
+    for (shadow, mut visibility) in shadow_query.iter_mut() {
+        let shown = cloud_query
+            .get(shadow.cloud)
+            .is_ok_and(|(_, cloud_visibility)| *cloud_visibility != Visibility::Hidden);
+        visibility.set_if_neq(draw(shown));
+    }
+
+    for (transform, mut visibility, mut mesh, lod) in island_query.iter_mut() {
+        let distance = transform.translation.distance(plane_position);
+        visibility.set_if_neq(draw(distance < world_config.island_draw_distance));
+
+        if let Some(lod) = lod {
+            let wanted = if distance < world_config.island_detail_distance { &lod.detailed } else { &lod.simple };
+            if mesh.0 != *wanted {
+                mesh.0 = wanted.clone();
+            }
+        }
+    }
+}
+
+// Leave a trail of wake patches behind the plane while it moves across the water.
+// Patches are dropped on a fixed interval so their number stays bounded.
+#[allow(clippy::too_many_arguments)]
+pub fn water_wake(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    world_config: Res<WorldConfig>,
+    plane_query: Query<(&Transform, &LinearVelocity, &CollidingEntities), With<Plane>>,
+    water_query: Query<Entity, With<Water>>,
+    mut spawn_timer: Local<Option<Timer>>,
+    mut wake_mesh: Local<Option<Handle<Mesh>>>,
+) {
+    let (Ok((plane_transform, linear_vel, colliding_entities)), Ok(water_entity)) =
+        (plane_query.get_single(), water_query.get_single())
+    else {
+        return;
+    };
+
+    let horizontal_velocity = linear_vel.0.with_y(0.0);
+    if !colliding_entities.contains(&water_entity) || horizontal_velocity.length() < WAKE_MIN_SPEED {
+        return;
+    }
+
+    let spawn_timer = spawn_timer
+        .get_or_insert_with(|| Timer::from_seconds(WAKE_SPAWN_INTERVAL, TimerMode::Repeating));
+    if !spawn_timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let mesh = wake_mesh
+        .get_or_insert_with(|| meshes.add(Rectangle::new(1.0, 1.0)))
+        .clone();
+    let material = materials.add(StandardMaterial {
+        base_color: Color::srgba(0.9, 0.95, 1.0, WAKE_MAX_ALPHA),
+        alpha_mode: AlphaMode::Blend,
+        unlit: true,
+        ..default()
+    });
+
+    // Lay the patch flat on the water, lined up with the direction of travel
+    let heading = Transform::default()
+        .looking_to(horizontal_velocity.normalize(), Vec3::Y)
+        .rotation;
+    let position = on_water_surface(&world_config, plane_transform.translation, time.elapsed_secs(), WAKE_HEIGHT);
+
+    commands.spawn((
+        Mesh3d(mesh),
+        MeshMaterial3d(material),
+        Transform::from_translation(position)
+            .with_rotation(heading * Quat::from_rotation_x(-PI / 2.0))
+            .with_scale(Vec3::splat(WAKE_START_SIZE)),
+        Wake {
+            lifetime: Timer::from_seconds(WAKE_LIFETIME, TimerMode::Once),
+        },
+        NotShadowCaster,
+    ));
+}
+
+// `position` moved to `offset` above the water surface beneath it
+fn on_water_surface(world_config: &WorldConfig, position: Vec3, time: f32, offset: f32) -> Vec3 {
+    position.with_y(water_surface_height(world_config, position.x, position.z, time) + offset)
+}
+
+// Spread and fade wake patches, riding the swell, despawning them when they've faded out
+pub fn update_wakes(
+    mut commands: Commands,
+    time: Res<Time>,
+    world_config: Res<WorldConfig>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut wake_query: Query<(Entity, &mut Wake, &mut Transform, &MeshMaterial3d<StandardMaterial>)>,
+) {
+    for (entity, mut wake, mut transform, material) in wake_query.iter_mut() {
+        if wake.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let progress = wake.lifetime.fraction();
+        transform.translation = on_water_surface(&world_config, transform.translation, time.elapsed_secs(), WAKE_HEIGHT);
+        transform.scale = Vec3::splat(WAKE_START_SIZE.lerp(WAKE_END_SIZE, progress));
+        if let Some(material) = materials.get_mut(&material.0) {
+            material.base_color.set_alpha(WAKE_MAX_ALPHA * (1.0 - progress));
+        }
+    }
+}
+
+// Send rings out over the water where the plane touches down, bigger for harder
+// impacts, and smaller ones at a steady interval while it's on the water and moving
+#[allow(clippy::too_many_arguments)]
+pub fn water_ripples(
+    mut commands: Commands,
+    time: Res<Time>,
+    world_config: Res<WorldConfig>,
+    plane_state: Res<PlaneState>,
+    mut impacts: EventReader<WaterImpact>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    plane_query: Query<(&Transform, &CollidingEntities), With<Plane>>,
+    water_query: Query<Entity, With<Water>>,
+    mut sailing_timer: Local<Option<Timer>>,
+    mut ripple_mesh: Local<Option<Handle<Mesh>>>,
+) {
+    let mut ripples: Vec<(Vec3, f32)> = impacts
+        .read()
+        .map(|impact| (impact.position, (impact.velocity * RIPPLE_SIZE_PER_IMPACT_SPEED).min(RIPPLE_MAX_SIZE)))
+        .collect();
+
+    if let (Ok((plane_transform, colliding_entities)), Ok(water_entity)) =
+        (plane_query.get_single(), water_query.get_single())
+    {
+        let sailing = colliding_entities.contains(&water_entity)
+            && plane_state.water_state != WaterState::Stopped;
+        let sailing_timer = sailing_timer
+            .get_or_insert_with(|| Timer::from_seconds(RIPPLE_SAILING_INTERVAL, TimerMode::Repeating));
+        if !sailing {
+            sailing_timer.reset();
+        } else if sailing_timer.tick(time.delta()).just_finished() {
+            ripples.push((plane_transform.translation, RIPPLE_SAILING_SIZE));
+        }
+    }
+
+    if ripples.is_empty() {
+        return;
+    }
+    let mesh = ripple_mesh
+        .get_or_insert_with(|| meshes.add(Annulus::new(1.0 - RIPPLE_THICKNESS, 1.0)))
+        .clone();
+
+    for (position, end_size) in ripples {
+        // Each ring fades on its own, so it gets its own material
+        let material = materials.add(StandardMaterial {
+            base_color: Color::srgba(0.9, 0.95, 1.0, RIPPLE_MAX_ALPHA),
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            ..default()
+        });
+        commands.spawn((
+            Mesh3d(mesh.clone()),
+            MeshMaterial3d(material),
+            // The annulus is built facing +Z; tip it flat onto the water
+            Transform::from_translation(on_water_surface(&world_config, position, time.elapsed_secs(), RIPPLE_HEIGHT))
+                .with_rotation(Quat::from_rotation_x(-PI / 2.0))
+                .with_scale(Vec3::splat(RIPPLE_START_SIZE)),
+            Ripple {
+                lifetime: Timer::from_seconds(RIPPLE_LIFETIME, TimerMode::Once),
+                end_size: end_size.max(RIPPLE_START_SIZE),
+            },
+            NotShadowCaster,
+        ));
+    }
+}
+
+// Grow and fade ripples, riding the swell, despawning them when they've faded out. The
+// ring slows as it spreads, like a real one.
+pub fn update_ripples(
+    mut commands: Commands,
+    time: Res<Time>,
+    world_config: Res<WorldConfig>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut ripple_query: Query<(Entity, &mut Ripple, &mut Transform, &MeshMaterial3d<StandardMaterial>)>,
+) {
+    for (entity, mut ripple, mut transform, material) in ripple_query.iter_mut() {
+        if ripple.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let progress = ripple.lifetime.fraction();
+        let spread = 1.0 - (1.0 - progress) * (1.0 - progress);
+        transform.translation = on_water_surface(&world_config, transform.translation, time.elapsed_secs(), RIPPLE_HEIGHT);
+        transform.scale = Vec3::splat(RIPPLE_START_SIZE.lerp(ripple.end_size, spread));
+        if let Some(material) = materials.get_mut(&material.0) {
+            material.base_color.set_alpha(RIPPLE_MAX_ALPHA * (1.0 - progress));
         }
     }
 }
@@ -0,0 +1,16 @@
+use bevy::prelude::*;
+
+// The plane hit the water hard enough to bounce
+#[derive(Event)]
+pub struct WaterImpact {
+    pub position: Vec3,
+    pub velocity: f32, // Downward speed at impact
+}
+
+// The plane set down on the water after a proper flight; `rating` is the landing
+// rating out of 100
+#[derive(Event)]
+pub struct Touchdown {
+    pub position: Vec3,
+    pub rating: f32,
+}
@@ -0,0 +1,121 @@
+use bevy::prelude::*;
+use avian3d::prelude::*;
+use crate::components::{Island, IslandKind, LandingTarget};
+use crate::resources::{GameMode, WorldConfig, WorldSeed};
+use crate::constants::{ISLAND_GRID_RESOLUTION, LANDING_TARGET_DISTANCE, LANDING_ZONE_RADIUS, LANDING_ZONE_FALLOFF};
+use crate::events::Touchdown;
+use crate::rings::spawn_rings;
+use crate::enemies::spawn_enemies;
+use crate::setup::island_shape;
+
+// Spawn whatever the selected game mode needs on top of the shared world
+pub fn mode_setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    game_mode: Res<GameMode>,
+    world_config: Res<WorldConfig>,
+    mut world_seed: ResMut<WorldSeed>,
+) {
+    info!("Starting game mode: {:?}", *game_mode);
+
+    match *game_mode {
+        GameMode::FreeFlight => {}
+        GameMode::Combat => spawn_enemies(&mut commands, &mut meshes, &mut materials),
+        GameMode::TimeTrial => spawn_rings(&mut commands, &mut meshes, &mut materials, world_config.spawn_radius, &mut world_seed.rng),
+        GameMode::Landing => {
+            // A highlighted atoll straight ahead of the spawn point to land beside
+            let (mesh, collider) = island_shape(IslandKind::Atoll, ISLAND_GRID_RESOLUTION);
+            commands.spawn((
+                Mesh3d(meshes.add(mesh)),
+                MeshMaterial3d(materials.add(StandardMaterial {
+                    base_color: Color::srgb(0.9, 0.75, 0.2),
+                    emissive: LinearRgba::rgb(0.6, 0.4, 0.0),
+                    perceptual_roughness: 0.9,
+                    ..default()
+                })),
+                Transform::from_xyz(0.0, 0.0, LANDING_TARGET_DISTANCE)
+                    .with_scale(Vec3::new(1.5, 0.75, 1.5)),
+                Island { kind: IslandKind::Atoll },
+                LandingTarget,
+                RigidBody::Static,
+                collider,
+            ));
+            commands.init_resource::<LandingChallenge>();
+        }
+    }
+}
+
+// Landing mode's results. Only exists in landing mode.
+#[derive(Resource, Default)]
+pub struct LandingChallenge {
+    pub last: Option<LandingScore>,
+    pub best: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LandingScore {
+    pub score: f32, // Out of 100
+    pub distance: f32, // Horizontal distance from the target's center at touchdown
+}
+
+// Share of the landing rating an approach keeps: all of it touching down inside the
+// landing zone around the target, falling away to none further out
+pub fn approach_factor(distance: f32) -> f32 {
+    1.0 - ((distance - LANDING_ZONE_RADIUS) / LANDING_ZONE_FALLOFF).clamp(0.0, 1.0)
+}
+
+// Score each touchdown in landing mode: the landing rating for how gently it set down,
+// scaled by how close to the target it did
+pub fn score_landing(
+    mut touchdowns: EventReader<Touchdown>,
+    challenge: Option<ResMut<LandingChallenge>>,
+    target_query: Query<&Transform, With<LandingTarget>>,
+) {
+    let (Some(mut challenge), Ok(target_transform)) = (challenge, target_query.get_single()) else {
+        touchdowns.clear();
+        return;
+    };
+
+    for touchdown in touchdowns.read() {
+        let distance = touchdown.position.xz().distance(target_transform.translation.xz());
+        let score = touchdown.rating * approach_factor(distance);
+        challenge.last = Some(LandingScore { score, distance });
+        challenge.best = challenge.best.max(score);
+        info!("Landing {:.0} m from the target scored {:.0}", distance, score);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+
+    // Scores a touchdown rated `rating` at `position` against a target at the origin
+    fn score_touchdown(position: Vec3, rating: f32) -> LandingChallenge {
+        let mut world = World::new();
+        world.init_resource::<Events<Touchdown>>();
+        world.init_resource::<LandingChallenge>();
+        world.spawn((LandingTarget, Transform::default()));
+        world.send_event(Touchdown { position, rating });
+        world.run_system_once(score_landing).unwrap();
+        world.remove_resource::<LandingChallenge>().unwrap()
+    }
+
+    #[test]
+    fn touchdown_in_the_zone_keeps_its_rating() {
+        let challenge = score_touchdown(Vec3::new(LANDING_ZONE_RADIUS * 0.5, 0.0, 0.0), 80.0);
+        assert_eq!(challenge.last.map(|last| last.score), Some(80.0));
+        assert_eq!(challenge.best, 80.0);
+    }
+
+    #[test]
+    fn score_falls_away_outside_the_zone() {
+        let halfway = LANDING_ZONE_RADIUS + LANDING_ZONE_FALLOFF / 2.0;
+        let challenge = score_touchdown(Vec3::new(0.0, 0.0, halfway), 80.0);
+        let last = challenge.last.unwrap();
+        assert!((last.score - 40.0).abs() < 1e-3);
+        assert!((last.distance - halfway).abs() < 1e-3);
+        assert_eq!(approach_factor(LANDING_ZONE_RADIUS + LANDING_ZONE_FALLOFF), 0.0);
+    }
+}
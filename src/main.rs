@@ -1,56 +1,326 @@
 mod atmospheric;
+mod audio;
+mod birds;
+mod chunks;
 mod components;
+mod config;
 mod constants;
+mod day_night;
+mod enemies;
+mod debug;
 mod environment_systems;
+mod events;
+mod game_modes;
+mod menu_systems;
+mod minimap;
+mod particles;
+mod replay;
 mod plane_systems;
 mod resources;
+mod rings;
+mod schedule;
+mod screenshot;
 mod setup;
+mod states;
+mod stats;
 mod ui;
+mod util;
+mod waves;
+mod weapons;
 
 use bevy::{
     prelude::*,
+    app::RunFixedMainLoopSystem,
     pbr::DirectionalLightShadowMap,
     core_pipeline::experimental::taa::TemporalAntiAliasPlugin,
 };
 use avian3d::prelude::*;
-use atmospheric::AtmosphericFogPlugin;
+use atmospheric::{AtmosphericFogPlugin, boost_bloom, speed_motion_blur};
 use constants::*;
-use resources::PlaneState;
+use resources::{GraphicsQuality, AntiAliasing, WorldSeed, GameRng, QuitSettings, ScreenshotSettings, QuitDialog, DebugPrintTimer, DebugOverlay, ZeroG, WorldConfig, WaterCollider, GameMode, HudDisplay, Turbulence};
 use setup::setup;
-use plane_systems::{plane_controller, plane_physics};
-use environment_systems::{camera_follow, cloud_movement};
-use ui::update_ui_display;
+use audio::{setup_audio, update_flight_audio, play_splash_sound};
+use plane_systems::{FlightPlugin, auto_throttle, refuel, spin_propeller};
+use environment_systems::{EnvironmentPlugin, update_wakes};
+use menu_systems::{quit_input, toggle_pause, pause_game, resume_game, setup_main_menu, cleanup_main_menu, main_menu_input};
+use minimap::update_minimap;
+use debug::{toggle_zero_g, restore_gravity, toggle_debug_overlay, update_debug_overlay};
+use game_modes::{mode_setup, score_landing};
+use states::GameState;
+use stats::{load_stats, save_stats};
+use schedule::GameSet;
+use screenshot::take_screenshot;
+use util::arg_value;
+use rings::{Score, ring_detection};
+use replay::{FlightRecorder, toggle_recording, record_flight, toggle_playback, play_back_flight};
+use birds::{bird_movement, bird_strikes};
+use enemies::enemy_ai;
+use weapons::{setup_weapons, fire_input, update_projectiles};
+use particles::{setup_particles, update_particles, damage_smoke, prop_wash, water_splash, contrail, thermal_debug_particles};
+use ui::{update_ui_display, update_records_display, update_landing_banner, update_g_vignette, update_quit_dialog, show_pause_overlay, hide_pause_overlay, update_hud_markers, update_flight_readouts, update_heading_display};
+
+// Settings picked on the command line, read before the app is built. Bad values fall
+// back to their defaults and leave a warning to log once logging is up.
+struct LaunchOptions {
+    world_config: WorldConfig,
+    turbulence: Turbulence,
+    game_mode: GameMode,
+    graphics_quality: GraphicsQuality,
+    anti_aliasing: AntiAliasing,
+    seed: u64,
+    screenshot_settings: ScreenshotSettings,
+    quit_settings: QuitSettings,
+    warnings: Vec<String>,
+}
+
+impl LaunchOptions {
+    fn from_args() -> Self {
+        let mut warnings = Vec::new();
+        let has_flag = |flag: &str| std::env::args().any(|arg| arg == flag);
+
+        // World size and density, for a small test world or a big one to explore
+        let world_size = arg_value("--world-size").map_or(WATER_SIZE, |size| match size.parse::<f32>() {
+            Ok(size) if size >= MIN_WORLD_SIZE => size,
+            _ => {
+                warnings.push(format!("Invalid world size '{}' (at least {}), using {}", size, MIN_WORLD_SIZE, WATER_SIZE));
+                WATER_SIZE
+            }
+        });
+        let mut world_config = WorldConfig::with_size(world_size);
+        if let Some(count) = arg_value("--islands") {
+            match count.parse() {
+                Ok(count) => world_config.island_count = count,
+                Err(_) => warnings.push(format!("Invalid island count '{}', using {}", count, world_config.island_count)),
+            }
+        }
+        if let Some(count) = arg_value("--clouds") {
+            match count.parse() {
+                Ok(count) => world_config.cloud_count = count,
+                Err(_) => warnings.push(format!("Invalid cloud count '{}', using {}", count, world_config.cloud_count)),
+            }
+        }
+
+        // Endless ocean: no edge to the world, islands and clouds generated around the plane
+        world_config.endless = has_flag("--endless");
+
+        // Model the water as a volume the plane can dive into instead of a hard surface
+        if has_flag("--water-volume") {
+            world_config.water_collider = WaterCollider::Volume { depth: WATER_VOLUME_DEPTH };
+        }
+
+        // Flat water for low-end machines
+        if has_flag("--no-waves") {
+            world_config.water_waves = false;
+        }
+
+        // Plain box clouds instead of the billboard puffs, for low-end machines
+        if has_flag("--cuboid-clouds") {
+            world_config.billboard_clouds = false;
+        }
+
+        // One flat sky color instead of the horizon to zenith gradient
+        if has_flag("--flat-sky") {
+            world_config.gradient_sky = false;
+        }
+
+        // Players who find the bumps near the water and in clouds distracting can start without them
+        let turbulence = Turbulence {
+            enabled: !has_flag("--no-turbulence"),
+        };
+
+        let game_mode = match arg_value("--mode") {
+            Some(mode) => GameMode::from_arg(&mode).unwrap_or_else(|| {
+                warnings.push(format!("Unknown game mode '{}', defaulting to free flight", mode));
+                GameMode::default()
+            }),
+            None => GameMode::default(),
+        };
+
+        let graphics_quality = match arg_value("--graphics") {
+            Some(quality) => GraphicsQuality::from_arg(&quality).unwrap_or_else(|| {
+                warnings.push(format!("Unknown graphics quality '{}', defaulting to high", quality));
+                GraphicsQuality::default()
+            }),
+            None => GraphicsQuality::default(),
+        };
+
+        // A fixed seed rebuilds the same world, for reproducing bugs
+        let seed = arg_value("--seed")
+            .or_else(|| std::env::var("AERO_ROSSO_SEED").ok())
+            .and_then(|seed| {
+                seed.parse()
+                    .map_err(|_| warnings.push(format!("Invalid world seed '{}', using a random one", seed)))
+                    .ok()
+            })
+            .unwrap_or_else(rand::random);
+
+        let anti_aliasing = match arg_value("--aa") {
+            Some(mode) => AntiAliasing::from_arg(&mode).unwrap_or_else(|| {
+                warnings.push(format!("Unknown anti-aliasing '{}', using the graphics quality's", mode));
+                graphics_quality.anti_aliasing()
+            }),
+            None => graphics_quality.anti_aliasing(),
+        };
+
+        let screenshot_settings = ScreenshotSettings {
+            include_hud: !has_flag("--screenshot-no-hud"),
+        };
+
+        let mut quit_settings = QuitSettings::default();
+        if let Some(key) = arg_value("--exit-key") {
+            match QuitSettings::exit_key_from_arg(&key) {
+                Some(key) => quit_settings.exit_key = key,
+                None => warnings.push(format!("Unknown exit key '{}', using escape", key)),
+            }
+        }
+
+        Self {
+            world_config,
+            turbulence,
+            game_mode,
+            graphics_quality,
+            anti_aliasing,
+            seed,
+            screenshot_settings,
+            quit_settings,
+            warnings,
+        }
+    }
+}
 
 fn main() {
     // Configure physics with interpolation for smooth movement
     let physics_plugins = PhysicsPlugins::default()
         .set(PhysicsInterpolationPlugin::interpolate_all());
 
-    App::new()
-        .add_plugins(DefaultPlugins)
-        .add_plugins(TemporalAntiAliasPlugin)
-        .add_plugins(AtmosphericFogPlugin)
+    let LaunchOptions {
+        world_config,
+        turbulence,
+        game_mode,
+        graphics_quality,
+        anti_aliasing,
+        seed,
+        screenshot_settings,
+        quit_settings,
+        warnings,
+    } = LaunchOptions::from_args();
+
+    let mut app = App::new();
+    app.add_plugins(DefaultPlugins);
+    // Logging is set up by DefaultPlugins, so the launch warnings join the rest of the log
+    for warning in &warnings {
+        warn!("{}", warning);
+    }
+    if anti_aliasing == AntiAliasing::Taa {
+        app.add_plugins(TemporalAntiAliasPlugin);
+    }
+    app.add_plugins(AtmosphericFogPlugin)
         .add_plugins(physics_plugins)
-        .insert_resource(DirectionalLightShadowMap { size: 4096 })
+        .add_plugins((FlightPlugin, EnvironmentPlugin))
+        .insert_resource(DirectionalLightShadowMap { size: graphics_quality.shadow_map_size() })
         .insert_resource(AmbientLight {
             color: Color::srgb(0.7, 0.8, 1.0),
             brightness: 0.5,
         })
-        .insert_resource(PlaneState {
-            speed: MIN_AIRSPEED,
-            momentum: Vec3::new(0.0, 0.0, -MIN_AIRSPEED),
-            turn_momentum: Vec3::ZERO,
-            bank_angle: 0.0,
-            was_on_water: false,
-            impact_bounce: 0.0,
-        })
-        .add_systems(Startup, setup)
+        .insert_resource(quit_settings)
+        .init_resource::<QuitDialog>()
+        .init_resource::<DebugPrintTimer>()
+        .init_resource::<DebugOverlay>()
+        .init_resource::<ZeroG>()
+        .init_resource::<HudDisplay>()
+        .init_resource::<Score>()
+        .insert_resource(turbulence)
+        .init_resource::<FlightRecorder>()
+        .insert_resource(world_config)
+        .insert_resource(game_mode)
+        .insert_resource(graphics_quality)
+        .insert_resource(anti_aliasing)
+        .insert_resource(WorldSeed::new(seed))
+        .insert_resource(GameRng::new(seed))
+        .insert_resource(screenshot_settings)
+        .init_state::<GameState>()
+        .add_systems(Startup, load_stats)
+        .add_systems(Last, save_stats)
+        .add_systems(OnEnter(GameState::MainMenu), setup_main_menu)
+        .add_systems(OnExit(GameState::MainMenu), cleanup_main_menu)
+        // The world is built when leaving the menu, not on every entry into Playing,
+        // since resuming from pause enters Playing again
+        .add_systems(
+            OnTransition { exited: GameState::MainMenu, entered: GameState::Playing },
+            (setup, setup_particles, setup_weapons, setup_audio, mode_setup).chain(),
+        )
+        .add_systems(OnEnter(GameState::Paused), (pause_game, show_pause_overlay))
+        .add_systems(OnExit(GameState::Paused), (resume_game, hide_pause_overlay))
+        // Input is read ahead of the fixed steps so a physics step integrates this frame's
+        // controls rather than last frame's; the remaining phases follow in Update
+        .configure_sets(RunFixedMainLoop, GameSet::Input.in_set(RunFixedMainLoopSystem::BeforeFixedMainLoop))
+        .configure_sets(Update, (
+            GameSet::PostPhysics,
+            GameSet::Camera,
+            GameSet::Ui,
+        ).chain())
+        // Flight physics steps at a fixed rate so handling is the same at any frame rate;
+        // avian interpolates the plane's transform between steps for rendering
+        .insert_resource(Time::<Fixed>::from_hz(PHYSICS_TIMESTEP_HZ))
+        // Everything that moves the world stops while paused; the HUD and menus keep running
+        .configure_sets(FixedUpdate, GameSet::Physics.run_if(in_state(GameState::Playing)))
+        .configure_sets(Update, (
+            GameSet::PostPhysics,
+            GameSet::Camera,
+        ).run_if(in_state(GameState::Playing)))
+        // There's no HUD to refresh until the world has been built
+        .configure_sets(Update, GameSet::Ui.run_if(not(in_state(GameState::MainMenu))))
+        .add_systems(RunFixedMainLoop, (
+            (
+                fire_input,
+                toggle_zero_g,
+                (toggle_recording, toggle_playback).chain(),
+            ).run_if(in_state(GameState::Playing)),
+            (main_menu_input.run_if(in_state(GameState::MainMenu)), toggle_pause, quit_input).chain(),
+            toggle_debug_overlay,
+            take_screenshot,
+        ).in_set(GameSet::Input))
+        .add_systems(FixedUpdate, (
+            restore_gravity.before(auto_throttle),
+            record_flight.after(refuel),
+        ).in_set(GameSet::Physics))
+        .add_systems(Update, (
+            ring_detection,
+            score_landing,
+            (bird_movement, bird_strikes).chain(),
+            enemy_ai,
+            update_projectiles,
+            play_back_flight,
+            spin_propeller,
+            damage_smoke,
+            prop_wash,
+            water_splash,
+            contrail,
+            thermal_debug_particles,
+            update_particles,
+            (update_flight_audio, play_splash_sound).chain(),
+            (boost_bloom, speed_motion_blur).chain(),
+        ).chain().after(update_wakes).in_set(GameSet::PostPhysics))
         .add_systems(Update, (
-            plane_controller,
-            plane_physics,
-            camera_follow,
-            cloud_movement,
-            update_ui_display,
-        ))
-        .run();
+            update_flight_readouts,
+            update_records_display,
+            update_landing_banner,
+            update_g_vignette,
+            update_heading_display,
+            update_minimap,
+            update_hud_markers,
+            update_quit_dialog,
+            update_debug_overlay,
+        ).chain().after(update_ui_display).in_set(GameSet::Ui));
+
+    // Report any Update systems left without a defined order in debug builds
+    #[cfg(debug_assertions)]
+    app.edit_schedule(Update, |schedule| {
+        schedule.set_build_settings(bevy::ecs::schedule::ScheduleBuildSettings {
+            ambiguity_detection: bevy::ecs::schedule::LogLevel::Warn,
+            ..default()
+        });
+    });
+
+    app.run();
 }
@@ -0,0 +1,222 @@
+use bevy::prelude::*;
+use avian3d::prelude::*;
+use crate::components::{MainMenuItem, MainMenuButton};
+use crate::resources::{QuitDialog, QuitSettings, Stats};
+use crate::stats::write_stats;
+use crate::states::GameState;
+use crate::constants::FORCE_QUIT_HOLD_TIME;
+use crate::util::InputRepeat;
+
+const MENU_BUTTON_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.7);
+const MENU_BUTTON_SELECTED_COLOR: Color = Color::srgba(0.6, 0.1, 0.1, 0.9);
+
+// Start screen: a title over Start and Quit buttons, with its own 2D camera
+pub fn setup_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("fonts/FiraMono-Medium.ttf");
+
+    commands.spawn((
+        Camera2d,
+        Camera {
+            clear_color: ClearColorConfig::Custom(Color::srgb(0.45, 0.65, 0.9)),
+            ..default()
+        },
+        MainMenuItem,
+    ));
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(16.0),
+                ..default()
+            },
+            MainMenuItem,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("AERO ROSSO"),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 64.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.1, 0.1)),
+                Node {
+                    margin: UiRect::bottom(Val::Px(24.0)),
+                    ..default()
+                },
+            ));
+
+            for (button, label) in [(MainMenuButton::Start, "Start"), (MainMenuButton::Quit, "Quit")] {
+                parent
+                    .spawn((
+                        Button,
+                        Node {
+                            width: Val::Px(220.0),
+                            padding: UiRect::all(Val::Px(12.0)),
+                            justify_content: JustifyContent::Center,
+                            ..default()
+                        },
+                        BackgroundColor(MENU_BUTTON_COLOR),
+                        button,
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((
+                            Text::new(label),
+                            TextFont {
+                                font: font.clone(),
+                                font_size: 28.0,
+                                ..default()
+                            },
+                            TextColor(Color::WHITE),
+                        ));
+                    });
+            }
+        });
+}
+
+pub fn cleanup_main_menu(mut commands: Commands, menu_query: Query<Entity, With<MainMenuItem>>) {
+    for entity in menu_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+// Pick a menu button with the mouse, or with Up/Down and Enter
+pub fn main_menu_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    time: Res<Time<Real>>,
+    mut button_query: Query<(&Interaction, &MainMenuButton, &mut BackgroundColor)>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut exit: EventWriter<AppExit>,
+    mut selected: Local<Option<MainMenuButton>>,
+    mut navigation: Local<(InputRepeat, InputRepeat)>,
+) {
+    let (up_repeat, down_repeat) = &mut *navigation;
+    let current = selected.get_or_insert(MainMenuButton::Start);
+    if up_repeat.tick(keyboard_input.pressed(KeyCode::ArrowUp), time.delta()) {
+        *current = MainMenuButton::Start;
+    }
+    if down_repeat.tick(keyboard_input.pressed(KeyCode::ArrowDown), time.delta()) {
+        *current = MainMenuButton::Quit;
+    }
+
+    let mut activated = keyboard_input.just_pressed(KeyCode::Enter).then_some(*current);
+    for (interaction, button, mut background) in button_query.iter_mut() {
+        match interaction {
+            Interaction::Pressed => activated = Some(*button),
+            Interaction::Hovered => *current = *button,
+            Interaction::None => {}
+        }
+        background.0 = if *button == *current { MENU_BUTTON_SELECTED_COLOR } else { MENU_BUTTON_COLOR };
+    }
+
+    match activated {
+        Some(MainMenuButton::Start) => next_state.set(GameState::Playing),
+        Some(MainMenuButton::Quit) => {
+            exit.send(AppExit::Success);
+        }
+        None => {}
+    }
+}
+
+// The exit key pauses and resumes the game. Ignored while the quit dialog is open,
+// where the same key dismisses the dialog instead.
+pub fn toggle_pause(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    quit_settings: Res<QuitSettings>,
+    quit_dialog: Res<QuitDialog>,
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if quit_dialog.open || !keyboard_input.just_pressed(quit_settings.exit_key) {
+        return;
+    }
+
+    match state.get() {
+        GameState::MainMenu => {}
+        GameState::Playing => next_state.set(GameState::Paused),
+        GameState::Paused => next_state.set(GameState::Playing),
+    }
+}
+
+// Stop the physics clock and sounds so nothing moves on while paused
+pub fn pause_game(
+    mut physics_time: ResMut<Time<Physics>>,
+    audio_query: Query<&AudioSink>,
+) {
+    physics_time.pause();
+    for sink in audio_query.iter() {
+        sink.pause();
+    }
+}
+
+pub fn resume_game(
+    mut physics_time: ResMut<Time<Physics>>,
+    audio_query: Query<&AudioSink>,
+) {
+    physics_time.unpause();
+    for sink in audio_query.iter() {
+        sink.play();
+    }
+}
+
+// Handles the exit key and the quit confirmation dialog. Every way out saves the
+// records first, so nothing is lost if the app is torn down before the `Last` schedule.
+#[allow(clippy::too_many_arguments)]
+pub fn quit_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    time: Res<Time<Real>>,
+    quit_settings: Res<QuitSettings>,
+    state: Res<State<GameState>>,
+    stats: Res<Stats>,
+    mut quit_dialog: ResMut<QuitDialog>,
+    mut exit: EventWriter<AppExit>,
+    mut navigation: Local<(InputRepeat, InputRepeat)>,
+) {
+    let exit_key = quit_settings.exit_key;
+    let mut save_and_exit = || {
+        write_stats(&stats);
+        exit.send(AppExit::Success);
+    };
+
+    // Holding the exit key skips the confirmation entirely
+    if keyboard_input.pressed(exit_key) {
+        quit_dialog.exit_key_held += time.delta_secs();
+        if quit_dialog.exit_key_held >= FORCE_QUIT_HOLD_TIME {
+            save_and_exit();
+            return;
+        }
+    } else if quit_dialog.exit_key_held > 0.0 {
+        quit_dialog.exit_key_held = 0.0;
+    }
+
+    if quit_dialog.open {
+        // Options are laid out left to right as "Quit" then "Keep flying"
+        let (left_repeat, right_repeat) = &mut *navigation;
+        if left_repeat.tick(keyboard_input.pressed(KeyCode::ArrowLeft), time.delta()) {
+            quit_dialog.quit_selected = true;
+        }
+        if right_repeat.tick(keyboard_input.pressed(KeyCode::ArrowRight), time.delta()) {
+            quit_dialog.quit_selected = false;
+        }
+
+        let confirmed = keyboard_input.just_pressed(KeyCode::Enter) && quit_dialog.quit_selected;
+        if confirmed || keyboard_input.just_pressed(KeyCode::KeyY) {
+            save_and_exit();
+        } else if keyboard_input.any_just_pressed([KeyCode::KeyN, KeyCode::Enter, exit_key]) {
+            quit_dialog.open = false;
+        }
+    } else if *state.get() == GameState::Paused && keyboard_input.just_pressed(KeyCode::KeyQ) {
+        // Quitting is offered from the pause overlay, since the exit key itself pauses
+        if quit_settings.confirm {
+            quit_dialog.open = true;
+            quit_dialog.quit_selected = false;
+        } else {
+            save_and_exit();
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use bevy::prelude::*;
+use crate::components::{Plane, Island, MinimapPanel, MinimapPlaneMarker, MinimapIslandDot};
+use crate::constants::*;
+use crate::resources::WorldConfig;
+use crate::util::compass_bearing;
+
+// Map a world position onto the minimap, in pixels from its top-left corner.
+// The map covers the whole water surface with north (-Z) at the top.
+fn minimap_position(world_pos: Vec3, world_size: f32) -> Vec2 {
+    Vec2::new(
+        (world_pos.x / world_size + 0.5) * MINIMAP_SIZE,
+        (world_pos.z / world_size + 0.5) * MINIMAP_SIZE,
+    )
+}
+
+// Minimap panel (bottom right): the panel border is the edge of the water and the
+// inner outline is the play area, beyond which the plane gets reset
+pub fn setup_minimap(commands: &mut Commands, world_config: &WorldConfig) {
+    let play_area_size =
+        world_config.play_area_half_extent * 2.0 / world_config.water_visual_size * MINIMAP_SIZE;
+    let play_area_inset = (MINIMAP_SIZE - play_area_size) / 2.0;
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(10.0),
+                right: Val::Px(10.0),
+                width: Val::Px(MINIMAP_SIZE),
+                height: Val::Px(MINIMAP_SIZE),
+                border: UiRect::all(Val::Px(2.0)),
+                overflow: Overflow::clip(),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.05, 0.2, 0.4, 0.7)),
+            BorderColor(Color::srgba(1.0, 1.0, 1.0, 0.8)),
+            MinimapPanel,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(play_area_inset),
+                    top: Val::Px(play_area_inset),
+                    width: Val::Px(play_area_size),
+                    height: Val::Px(play_area_size),
+                    border: UiRect::all(Val::Px(1.0)),
+                    ..default()
+                },
+                BorderColor(Color::srgba(1.0, 1.0, 1.0, 0.3)),
+            ));
+
+            // The plane marker's white tip shows which way it's heading
+            parent
+                .spawn((
+                    Node {
+                        position_type: PositionType::Absolute,
+                        width: Val::Px(MINIMAP_PLANE_MARKER_WIDTH),
+                        height: Val::Px(MINIMAP_PLANE_MARKER_LENGTH),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.9, 0.1, 0.1)),
+                    ZIndex(1),
+                    MinimapPlaneMarker,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Node {
+                            width: Val::Percent(100.0),
+                            height: Val::Px(MINIMAP_PLANE_MARKER_WIDTH),
+                            ..default()
+                        },
+                        BackgroundColor(Color::WHITE),
+                    ));
+                });
+        });
+}
+
+// Keep the minimap in step with the world: a dot per island (added and removed as
+// islands come and go) and the plane marker at its position, turned to its heading
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn update_minimap(
+    mut commands: Commands,
+    world_config: Res<WorldConfig>,
+    panel_query: Query<Entity, With<MinimapPanel>>,
+    plane_query: Query<&Transform, With<Plane>>,
+    island_query: Query<&Transform, With<Island>>,
+    new_island_query: Query<Entity, Added<Island>>,
+    mut dot_query: Query<(Entity, &MinimapIslandDot, &mut Node), Without<MinimapPlaneMarker>>,
+    mut marker_query: Query<(&mut Node, &mut Transform), (With<MinimapPlaneMarker>, Without<Plane>, Without<Island>)>,
+) {
+    let Ok(panel) = panel_query.get_single() else {
+        return;
+    };
+    let world_size = world_config.water_visual_size;
+
+    for island in new_island_query.iter() {
+        commands.entity(panel).with_children(|parent| {
+            parent.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    width: Val::Px(MINIMAP_ISLAND_DOT_SIZE),
+                    height: Val::Px(MINIMAP_ISLAND_DOT_SIZE),
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(0.3, 0.8, 0.3)),
+                BorderRadius::MAX,
+                MinimapIslandDot { island },
+            ));
+        });
+    }
+
+    for (dot_entity, dot, mut node) in dot_query.iter_mut() {
+        let Ok(island_transform) = island_query.get(dot.island) else {
+            commands.entity(dot_entity).despawn_recursive();
+            continue;
+        };
+        let position = minimap_position(island_transform.translation, world_size)
+            - Vec2::splat(MINIMAP_ISLAND_DOT_SIZE / 2.0);
+        node.left = Val::Px(position.x);
+        node.top = Val::Px(position.y);
+    }
+
+    let (Ok(plane_transform), Ok((mut marker_node, mut marker_transform))) =
+        (plane_query.get_single(), marker_query.get_single_mut())
+    else {
+        return;
+    };
+
+    // Clamp to the edge of the map so the plane is never lost off it
+    let half_marker = Vec2::new(MINIMAP_PLANE_MARKER_WIDTH, MINIMAP_PLANE_MARKER_LENGTH) / 2.0;
+    let position = minimap_position(plane_transform.translation, world_size)
+        .clamp(Vec2::ZERO, Vec2::splat(MINIMAP_SIZE))
+        - half_marker;
+    marker_node.left = Val::Px(position.x);
+    marker_node.top = Val::Px(position.y);
+
+    // UI space has y pointing down, so a positive rotation turns the marker clockwise
+    if let Some(bearing) = compass_bearing(*plane_transform.forward()) {
+        marker_transform.rotation = Quat::from_rotation_z(bearing.to_radians());
+    }
+}
@@ -0,0 +1,368 @@
+use bevy::{
+    prelude::*,
+    pbr::NotShadowCaster,
+};
+use rand::Rng;
+use crate::components::{Particle, Plane, Water, Contrail, Thermal};
+use avian3d::prelude::*;
+use crate::resources::{PlaneState, FlightConfig, DebugOverlay, GameRng};
+use crate::events::WaterImpact;
+use crate::constants::*;
+
+// Engine position in plane-local space (the propeller hub)
+const ENGINE_OFFSET: Vec3 = Vec3::new(0.0, 0.0, 2.1);
+
+// Contrail source in plane-local space (the tail tip)
+const TAIL_OFFSET: Vec3 = Vec3::new(0.0, 1.0, -2.0);
+
+// Shared mesh and materials for all particle effects
+#[derive(Resource)]
+pub struct ParticleAssets {
+    pub mesh: Handle<Mesh>,
+    pub smoke: Handle<StandardMaterial>,
+    pub fire: Handle<StandardMaterial>,
+    pub spray: Handle<StandardMaterial>,
+    pub contrail: Handle<StandardMaterial>,
+    pub feather: Handle<StandardMaterial>,
+}
+
+pub fn setup_particles(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.insert_resource(ParticleAssets {
+        mesh: meshes.add(Sphere::new(0.5).mesh().uv(8, 6)),
+        smoke: materials.add(StandardMaterial {
+            base_color: Color::srgba(0.15, 0.15, 0.15, 0.6),
+            perceptual_roughness: 1.0,
+            alpha_mode: AlphaMode::Blend,
+            ..default()
+        }),
+        fire: materials.add(StandardMaterial {
+            base_color: Color::srgb(1.0, 0.5, 0.1),
+            emissive: LinearRgba::rgb(8.0, 3.0, 0.5),
+            unlit: true,
+            ..default()
+        }),
+        spray: materials.add(StandardMaterial {
+            base_color: Color::srgba(0.9, 0.95, 1.0, 0.5),
+            perceptual_roughness: 0.6,
+            alpha_mode: AlphaMode::Blend,
+            ..default()
+        }),
+        contrail: materials.add(StandardMaterial {
+            base_color: Color::srgba(1.0, 1.0, 1.0, 0.35),
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            ..default()
+        }),
+        feather: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.85, 0.85, 0.8),
+            perceptual_roughness: 1.0,
+            ..default()
+        }),
+    });
+}
+
+pub fn spawn_particle(
+    commands: &mut Commands,
+    mesh: &Handle<Mesh>,
+    material: &Handle<StandardMaterial>,
+    position: Vec3,
+    particle: Particle,
+) -> Entity {
+    commands.spawn((
+        Mesh3d(mesh.clone()),
+        MeshMaterial3d(material.clone()),
+        Transform::from_translation(position).with_scale(Vec3::splat(particle.start_size)),
+        NotShadowCaster,
+        particle,
+    )).id()
+}
+
+// Move particles, resize them over their lifetime and despawn them when they expire
+pub fn update_particles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut particle_query: Query<(Entity, &mut Transform, &mut Particle)>,
+) {
+    let dt = time.delta_secs();
+
+    for (entity, mut transform, mut particle) in particle_query.iter_mut() {
+        if particle.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        particle.velocity.y -= particle.gravity * dt;
+        transform.translation += particle.velocity * dt;
+        let size = particle.start_size.lerp(particle.end_size, particle.lifetime.fraction());
+        transform.scale = Vec3::splat(size);
+    }
+}
+
+// Trail smoke from the engine once the plane is damaged, and flames when it's critical
+#[allow(clippy::too_many_arguments)]
+pub fn damage_smoke(
+    mut commands: Commands,
+    time: Res<Time>,
+    plane_state: Res<PlaneState>,
+    particle_assets: Res<ParticleAssets>,
+    mut game_rng: ResMut<GameRng>,
+    plane_query: Query<&Transform, With<Plane>>,
+    mut smoke_accumulator: Local<f32>,
+    mut fire_accumulator: Local<f32>,
+) {
+    let Ok(plane_transform) = plane_query.get_single() else {
+        return;
+    };
+
+    let health_fraction = (plane_state.health / MAX_HEALTH).clamp(0.0, 1.0);
+    if health_fraction >= SMOKE_HEALTH_FRACTION {
+        *smoke_accumulator = 0.0;
+        *fire_accumulator = 0.0;
+        return;
+    }
+
+    // Ease in so light damage gives a faint wisp and heavy damage a thick plume
+    let damage = 1.0 - health_fraction / SMOKE_HEALTH_FRACTION;
+    let intensity = damage * damage;
+
+    let dt = time.delta_secs();
+    let engine_position = plane_transform.transform_point(ENGINE_OFFSET);
+    let rng = game_rng.rng();
+
+    *smoke_accumulator += SMOKE_MAX_RATE * intensity * dt;
+    while *smoke_accumulator >= 1.0 {
+        *smoke_accumulator -= 1.0;
+        let drift = Vec3::new(
+            rng.gen_range(-0.5..0.5),
+            rng.gen_range(1.0..2.5),
+            rng.gen_range(-0.5..0.5),
+        );
+        spawn_particle(
+            &mut commands,
+            &particle_assets.mesh,
+            &particle_assets.smoke,
+            engine_position,
+            Particle {
+                velocity: drift,
+                lifetime: Timer::from_seconds(rng.gen_range(1.5..3.0), TimerMode::Once),
+                start_size: 0.8 + intensity,
+                end_size: 3.0 + 3.0 * intensity,
+                gravity: 0.0,
+            },
+        );
+    }
+
+    if health_fraction >= FIRE_HEALTH_FRACTION {
+        *fire_accumulator = 0.0;
+        return;
+    }
+
+    *fire_accumulator += FIRE_RATE * dt;
+    while *fire_accumulator >= 1.0 {
+        *fire_accumulator -= 1.0;
+        let flicker = Vec3::new(
+            rng.gen_range(-0.3..0.3),
+            rng.gen_range(0.5..1.5),
+            rng.gen_range(-0.3..0.3),
+        );
+        spawn_particle(
+            &mut commands,
+            &particle_assets.mesh,
+            &particle_assets.fire,
+            engine_position,
+            Particle {
+                velocity: flicker,
+                lifetime: Timer::from_seconds(rng.gen_range(0.2..0.5), TimerMode::Once),
+                start_size: 0.9,
+                end_size: 0.1,
+                gravity: 0.0,
+            },
+        );
+    }
+}
+
+// Kick up spray under the propeller when flying low or taxiing.
+// Stronger with more throttle and fading out as the plane climbs.
+#[allow(clippy::too_many_arguments)]
+pub fn prop_wash(
+    mut commands: Commands,
+    time: Res<Time>,
+    plane_state: Res<PlaneState>,
+    flight_config: Res<FlightConfig>,
+    particle_assets: Res<ParticleAssets>,
+    mut game_rng: ResMut<GameRng>,
+    plane_query: Query<&Transform, With<Plane>>,
+    mut spray_accumulator: Local<f32>,
+) {
+    let Ok(plane_transform) = plane_query.get_single() else {
+        return;
+    };
+
+    let altitude = plane_transform.translation.y.max(0.0);
+    let throttle = (plane_state.speed / flight_config.max_airspeed).clamp(0.0, 1.0);
+    let intensity = throttle * (1.0 - altitude / PROP_WASH_MAX_ALTITUDE).clamp(0.0, 1.0);
+    if intensity <= 0.0 {
+        *spray_accumulator = 0.0;
+        return;
+    }
+
+    let engine_position = plane_transform.transform_point(ENGINE_OFFSET);
+    let wash_center = Vec3::new(engine_position.x, 0.1, engine_position.z);
+    let rng = game_rng.rng();
+
+    *spray_accumulator += PROP_WASH_RATE * intensity * time.delta_secs();
+    while *spray_accumulator >= 1.0 {
+        *spray_accumulator -= 1.0;
+
+        // Blow outward from the point under the propeller
+        let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+        let outward = Vec3::new(angle.cos(), 0.0, angle.sin());
+        let spread = rng.gen_range(0.0..1.5);
+        spawn_particle(
+            &mut commands,
+            &particle_assets.mesh,
+            &particle_assets.spray,
+            wash_center + outward * spread,
+            Particle {
+                velocity: outward * rng.gen_range(2.0..5.0) * intensity
+                    + Vec3::Y * rng.gen_range(0.5..2.0),
+                lifetime: Timer::from_seconds(rng.gen_range(0.4..0.8), TimerMode::Once),
+                start_size: 0.3,
+                end_size: 0.8,
+                gravity: 0.0,
+            },
+        );
+    }
+}
+
+// Burst of spray where the plane hits the water; harder impacts throw more of it
+pub fn water_splash(
+    mut commands: Commands,
+    mut impacts: EventReader<WaterImpact>,
+    particle_assets: Res<ParticleAssets>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    let rng = game_rng.rng();
+
+    for impact in impacts.read() {
+        let count = ((impact.velocity * SPLASH_PARTICLES_PER_SPEED) as usize).min(SPLASH_MAX_PARTICLES);
+        let center = impact.position.with_y(0.0);
+
+        for _ in 0..count {
+            let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+            let outward = Vec3::new(angle.cos(), 0.0, angle.sin());
+            spawn_particle(
+                &mut commands,
+                &particle_assets.mesh,
+                &particle_assets.spray,
+                center + outward * rng.gen_range(0.0..2.0),
+                Particle {
+                    velocity: outward * rng.gen_range(2.0..6.0)
+                        + Vec3::Y * rng.gen_range(0.5..1.0) * impact.velocity,
+                    lifetime: Timer::from_seconds(rng.gen_range(0.7..1.1), TimerMode::Once),
+                    start_size: 0.5,
+                    end_size: 1.2,
+                    gravity: SPRAY_GRAVITY,
+                },
+            );
+        }
+    }
+}
+
+// Thin vapor trail from the tail when flying high and fast. The number of trail
+// particles alive at once is capped.
+#[allow(clippy::too_many_arguments)]
+pub fn contrail(
+    mut commands: Commands,
+    time: Res<Time>,
+    plane_state: Res<PlaneState>,
+    particle_assets: Res<ParticleAssets>,
+    plane_query: Query<(&Transform, &CollidingEntities), With<Plane>>,
+    water_query: Query<Entity, With<Water>>,
+    contrail_query: Query<(), With<Contrail>>,
+    mut trail_accumulator: Local<f32>,
+) {
+    let Ok((plane_transform, colliding_entities)) = plane_query.get_single() else {
+        return;
+    };
+    let is_on_water = water_query
+        .get_single()
+        .is_ok_and(|water_entity| colliding_entities.contains(&water_entity));
+
+    if is_on_water
+        || plane_transform.translation.y < CONTRAIL_MIN_ALTITUDE
+        || plane_state.speed < CONTRAIL_MIN_SPEED
+    {
+        *trail_accumulator = 0.0;
+        return;
+    }
+
+    let tail_position = plane_transform.transform_point(TAIL_OFFSET);
+    let mut active = contrail_query.iter().count();
+
+    *trail_accumulator += CONTRAIL_RATE * time.delta_secs();
+    while *trail_accumulator >= 1.0 && active < CONTRAIL_MAX_PARTICLES {
+        *trail_accumulator -= 1.0;
+        active += 1;
+
+        let particle = spawn_particle(
+            &mut commands,
+            &particle_assets.mesh,
+            &particle_assets.contrail,
+            tail_position,
+            Particle {
+                velocity: Vec3::ZERO,
+                lifetime: Timer::from_seconds(CONTRAIL_LIFETIME, TimerMode::Once),
+                start_size: 0.6,
+                end_size: 0.1,
+                gravity: 0.0,
+            },
+        );
+        commands.entity(particle).insert(Contrail);
+    }
+    *trail_accumulator = trail_accumulator.min(1.0);
+}
+
+// With the debug overlay on, faint motes rise through each thermal to show where it is
+pub fn thermal_debug_particles(
+    mut commands: Commands,
+    time: Res<Time>,
+    debug_overlay: Res<DebugOverlay>,
+    particle_assets: Res<ParticleAssets>,
+    mut game_rng: ResMut<GameRng>,
+    thermal_query: Query<&Thermal>,
+    mut mote_accumulator: Local<f32>,
+) {
+    if !debug_overlay.enabled {
+        *mote_accumulator = 0.0;
+        return;
+    }
+
+    let rng = game_rng.rng();
+    *mote_accumulator += THERMAL_DEBUG_PARTICLE_RATE * time.delta_secs();
+    while *mote_accumulator >= 1.0 {
+        *mote_accumulator -= 1.0;
+
+        for thermal in thermal_query.iter() {
+            let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+            let offset = Vec3::new(angle.cos(), 0.0, angle.sin()) * rng.gen_range(0.0..thermal.radius);
+            spawn_particle(
+                &mut commands,
+                &particle_assets.mesh,
+                &particle_assets.contrail,
+                thermal.position + offset + Vec3::Y * rng.gen_range(5.0..40.0),
+                Particle {
+                    velocity: Vec3::Y * thermal.strength * 2.0,
+                    lifetime: Timer::from_seconds(3.0, TimerMode::Once),
+                    start_size: 1.0,
+                    end_size: 0.3,
+                    gravity: 0.0,
+                },
+            );
+        }
+    }
+}
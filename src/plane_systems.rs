@@ -1,63 +1,210 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, input::mouse::MouseMotion, window::{CursorGrabMode, PrimaryWindow}};
 use avian3d::prelude::*;
 use std::f32::consts::PI;
-use crate::components::{Plane, Water};
-use crate::resources::PlaneState;
+use crate::components::{Plane, Water, Propeller, Island, Thermal, SpectatorCamera, Cloud};
+use crate::resources::{PlaneState, KeyBindings, KeyboardAxes, CameraShake, Fuel, Wind, MouseFlight, WorldConfig, WaterCollider, AutoThrottle, FlightConfig, Stats, LandingRating, WaterState, Turbulence, GameRng};
 use crate::constants::*;
+use crate::events::{WaterImpact, Touchdown};
+use crate::waves::water_surface_height;
+use crate::util::smoothing;
+use crate::states::GameState;
+use rand::Rng;
+use crate::config::load_flight_config;
+use crate::schedule::GameSet;
+use crate::ui::update_ui_display;
 
+// The player's plane: its flight state and tuning, controls, physics, and HUD readout.
+//
+// Ordering: plane_controller writes PlaneState and the plane's angular velocity, and
+// plane_physics reads both, so the controller must finish first or the physics would
+// integrate last frame's stick. The controller runs in GameSet::Input, chained after the
+// other control toggles, which sits in RunFixedMainLoop ahead of the fixed steps.
+// plane_physics runs in GameSet::Physics (FixedUpdate) ahead of the forces that add to
+// its momentum. It puts the angular velocity back to the controller's rate at the start
+// of every step, so what the stall, boundary and turbulence add lasts only that step.
+// camera_follow and update_ui_display read the result in Update, in GameSet::Camera and
+// GameSet::Ui, after every fixed step of the frame has run.
+pub struct FlightPlugin;
+
+impl Plugin for FlightPlugin {
+    fn build(&self, app: &mut App) {
+        // Defaults until load_flight_config reads config/flight.ron at startup
+        let flight_config = FlightConfig::default();
+        app.insert_resource(PlaneState::new(&flight_config))
+            .insert_resource(Fuel { amount: flight_config.fuel_capacity })
+            .insert_resource(flight_config)
+            .init_resource::<KeyBindings>()
+            .init_resource::<AutoThrottle>()
+            .init_resource::<MouseFlight>()
+            .init_resource::<KeyboardAxes>()
+            .init_resource::<Turbulence>()
+            .init_resource::<Stats>()
+            .init_resource::<LandingRating>()
+            .add_event::<WaterImpact>()
+            .add_event::<Touchdown>()
+            .add_systems(Startup, load_flight_config)
+            .add_systems(RunFixedMainLoop, (
+                (reset_input, toggle_mouse_flight, toggle_autopilot, auto_throttle_input, plane_controller).chain(),
+                toggle_turbulence,
+            ).run_if(in_state(GameState::Playing)).in_set(GameSet::Input))
+            .add_systems(FixedUpdate, (
+                auto_throttle,
+                plane_physics,
+                thermal_lift,
+                turbulence,
+                measure_g_force,
+                island_collisions,
+                refuel,
+            ).chain().in_set(GameSet::Physics))
+            .add_systems(Update, update_ui_display.in_set(GameSet::Ui));
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn plane_controller(
     keyboard_input: Res<ButtonInput<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
     mut plane_state: ResMut<PlaneState>,
+    flight_config: Res<FlightConfig>,
+    auto_throttle: Res<AutoThrottle>,
+    fuel: Res<Fuel>,
+    mouse_flight: Res<MouseFlight>,
+    mut mouse_motion: EventReader<MouseMotion>,
+    mut mouse_stick: Local<Vec2>,
+    mut keyboard_axes: ResMut<KeyboardAxes>,
     time: Res<Time>,
+    gamepads: Query<&Gamepad>,
     mut query: Query<(&Transform, &mut AngularVelocity, &CollidingEntities), With<Plane>>,
     water_query: Query<Entity, With<Water>>,
+    spectator_query: Query<(), With<SpectatorCamera>>,
 ) {
-    let (_plane_transform, mut angular_vel, colliding_entities) = query.single_mut();
+    if plane_state.crashed {
+        return;
+    }
+    let Ok((plane_transform, mut angular_vel, colliding_entities)) = query.get_single_mut() else {
+        return;
+    };
     let dt = time.delta_secs();
-    let water_entity = water_query.single();
-    let is_on_water = colliding_entities.contains(&water_entity);
+    let is_on_water = water_query
+        .get_single()
+        .is_ok_and(|water_entity| colliding_entities.contains(&water_entity));
 
-    // Airspeed control (Up/Down arrows)
-    if keyboard_input.pressed(KeyCode::ArrowUp) {
-        plane_state.speed += ACCELERATION * dt;
-        plane_state.speed = plane_state.speed.min(MAX_AIRSPEED);
+    // While spectating the keys and mouse fly the camera, so the plane gets no input
+    // and flies on hands-off (or on the autopilot)
+    let spectating = !spectator_query.is_empty();
+    let no_input = ButtonInput::default();
+    let (keyboard_input, gamepad) = if spectating {
+        (&no_input, None)
+    } else {
+        (&*keyboard_input, gamepads.iter().next())
+    };
+
+    // Boost drains its meter for a higher top speed and harder acceleration, and the
+    // meter recharges while it's off
+    plane_state.boosting = keyboard_input.pressed(key_bindings.boost)
+        && plane_state.boost_energy > 0.0
+        && fuel.amount > 0.0
+        && !is_on_water;
+    if plane_state.boosting {
+        plane_state.boost_energy = (plane_state.boost_energy - BOOST_DRAIN_RATE * dt).max(0.0);
+        let boost_cap = flight_config.max_airspeed * BOOST_SPEED_FACTOR;
+        if plane_state.speed < boost_cap {
+            plane_state.speed = (plane_state.speed + flight_config.acceleration * BOOST_ACCELERATION_FACTOR * dt).min(boost_cap);
+        }
+    } else {
+        plane_state.boost_energy = (plane_state.boost_energy + BOOST_RECHARGE_RATE * dt).min(1.0);
+        // Ease back down to the normal top speed rather than snapping to it
+        if plane_state.speed > flight_config.max_airspeed {
+            plane_state.speed = (plane_state.speed - BOOST_EASE_RATE * dt).max(flight_config.max_airspeed);
+        }
     }
-    if keyboard_input.pressed(KeyCode::ArrowDown) {
-        plane_state.speed -= ACCELERATION * dt;
-        plane_state.speed = plane_state.speed.max(MIN_AIRSPEED);
+
+    // Airspeed control (throttle keys or triggers), unless the auto-throttle has the power
+    // or the engine is out of fuel
+    let throttle = throttle_input(keyboard_input, &key_bindings, gamepad);
+    let manual_power = !auto_throttle.enabled && fuel.amount > 0.0;
+    // The auto-throttle reports its own power changes when it steps
+    plane_state.throttle = if manual_power { throttle } else { 0.0 };
+    if manual_power {
+        // Above the normal top speed (while boosting or easing off it) the throttle can't add more
+        if throttle > 0.0 && plane_state.speed < flight_config.max_airspeed {
+            plane_state.speed += throttle * flight_config.acceleration * dt;
+            plane_state.speed = plane_state.speed.min(flight_config.max_airspeed);
+        } else if throttle < 0.0 {
+            // On the water the plane can be throttled right back to a stop
+            let floor = if is_on_water { 0.0 } else { flight_config.min_airspeed };
+            plane_state.speed += throttle * flight_config.acceleration * dt;
+            plane_state.speed = plane_state.speed.max(floor);
+        }
     }
 
-    // Get control inputs
-    let roll: f32 = if keyboard_input.pressed(KeyCode::KeyA) {
-        -1.0
-    } else if keyboard_input.pressed(KeyCode::KeyD) {
-        1.0
+    // In mouse mode the mouse moves a virtual stick that drifts back to center.
+    // Holding V for free look hands the mouse to the camera instead.
+    let mouse_delta: Vec2 = mouse_motion.read().map(|motion| motion.delta).sum();
+    if mouse_flight.enabled && !keyboard_input.pressed(KeyCode::KeyV) && !spectating {
+        *mouse_stick = (*mouse_stick + mouse_delta * flight_config.mouse_sensitivity)
+            .clamp(Vec2::NEG_ONE, Vec2::ONE);
+    }
+    *mouse_stick *= (-MOUSE_RECENTER_RATE * dt).exp();
+    let mouse = if mouse_flight.enabled {
+        Vec2::new(
+            apply_dead_zone(mouse_stick.x, MOUSE_DEAD_ZONE),
+            apply_dead_zone(mouse_stick.y, MOUSE_DEAD_ZONE),
+        )
     } else {
-        0.0
+        Vec2::ZERO
     };
 
-    let pitch = if keyboard_input.pressed(KeyCode::KeyW) {
-        -1.0
-    } else if keyboard_input.pressed(KeyCode::KeyS) {
-        1.0
-    } else {
-        0.0
-    };
+    // The keys ease in and out (unless smoothing is off) so they can make fine corrections
+    let key_roll = key_axis(keyboard_input, key_bindings.roll_left, key_bindings.roll_right);
+    let key_pitch = key_axis(keyboard_input, key_bindings.pitch_down, key_bindings.pitch_up);
+    let key_yaw = key_axis(keyboard_input, key_bindings.yaw_left, key_bindings.yaw_right);
+    keyboard_axes.roll = smooth_key_axis(keyboard_axes.roll, key_roll, &flight_config, dt);
+    keyboard_axes.pitch = smooth_key_axis(keyboard_axes.pitch, key_pitch, &flight_config, dt);
+    keyboard_axes.yaw = smooth_key_axis(keyboard_axes.yaw, key_yaw, &flight_config, dt);
 
-    let yaw = if keyboard_input.pressed(KeyCode::KeyQ) {
-        -1.0
-    } else if keyboard_input.pressed(KeyCode::KeyE) {
-        1.0
+    // Get control inputs. Keys win over the sticks; the sticks give proportional input.
+    let roll = key_or_stick(
+        keyboard_axes.roll,
+        stick_axis(gamepad, GamepadAxis::LeftStickX) + mouse.x,
+    );
+
+    // Trim holds a pitch input hands-off, for a steady climb or descent
+    plane_state.trim_pitch = (plane_state.trim_pitch
+        + key_axis(keyboard_input, key_bindings.trim_down, key_bindings.trim_up) * TRIM_RATE * dt)
+        .clamp(-TRIM_LIMIT, TRIM_LIMIT);
+
+    // Pushing the stick forward lowers the nose, like the pitch-down key; so does moving the mouse up
+    let manual_pitch = key_or_stick(
+        keyboard_axes.pitch,
+        -stick_axis(gamepad, GamepadAxis::LeftStickY) + mouse.y,
+    );
+
+    let yaw = key_or_stick(
+        keyboard_axes.yaw,
+        stick_axis(gamepad, GamepadAxis::RightStickX) + mouse.x * MOUSE_YAW_MIX,
+    );
+
+    // Any hands-on input takes over from the autopilot
+    if plane_state.autopilot && (roll != 0.0 || manual_pitch != 0.0 || yaw != 0.0) {
+        plane_state.autopilot = false;
+        info!("Autopilot disengaged");
+    }
+
+    // The autopilot only flies the pitch. With no roll input the auto-level below
+    // brings the wings level, which stops the turn and holds the heading.
+    let pitch = if plane_state.autopilot {
+        autopilot_pitch(plane_transform, plane_state.autopilot_altitude)
     } else {
-        0.0
+        (manual_pitch + plane_state.trim_pitch).clamp(-1.0, 1.0)
     };
 
     // Reduce control sensitivity when on water
     let control_multiplier = if is_on_water { 0.5 } else { 1.0 };
 
-    // Calculate base roll sensitivity based on speed
-    let speed_factor = (plane_state.speed - MIN_AIRSPEED) / (MAX_AIRSPEED - MIN_AIRSPEED);
-    let base_sensitivity = BASE_ROLL_SENSITIVITY * (0.5 + speed_factor * 0.5);
+    // Control surfaces bite harder the faster the plane goes
+    let authority = control_authority(plane_state.speed, &flight_config);
+    let base_sensitivity = flight_config.base_roll_sensitivity * authority;
     
     // Calculate roll resistance based on current bank angle
     let bank_resistance = (plane_state.bank_angle.abs() * 16.0).exp();
@@ -78,58 +225,332 @@ pub fn plane_controller(
     if roll == 0.0 || is_on_water {
         let level_factor = plane_state.bank_angle.abs() / (PI / 3.0);
         let level_speed = if is_on_water {
-            WATER_LEVEL_SPEED
+            flight_config.water_level_speed
         } else {
-            AUTO_LEVEL_SPEED * (0.8 + level_factor * 0.8)
+            flight_config.auto_level_speed * (0.8 + level_factor * 0.8)
         };
-        plane_state.bank_angle *= 1.0 - level_speed * dt;
+        plane_state.bank_angle *= level_decay(level_speed, dt);
     }
 
     // Calculate turn rate based on bank angle
-    let bank_turn = plane_state.bank_angle * BANK_TURN_RATIO;
-    let total_turn = yaw * YAW_SENSITIVITY + bank_turn;
+    let bank_turn = plane_state.bank_angle * flight_config.bank_turn_ratio;
+    let total_turn = yaw * flight_config.yaw_sensitivity * authority + bank_turn;
 
     // Update turn momentum
     let target_turn = Vec3::new(
-        pitch * PITCH_SENSITIVITY,
-        total_turn * TURN_SPEED,
+        pitch * flight_config.pitch_sensitivity * authority,
+        total_turn * flight_config.turn_speed,
         0.0
     ) * control_multiplier;
     
-    plane_state.turn_momentum = plane_state.turn_momentum.lerp(target_turn, 1.0 - TURN_MOMENTUM);
+    plane_state.turn_momentum = plane_state.turn_momentum.lerp(target_turn, smoothing(flight_config.turn_response_time, dt));
 
     // Apply rotations through angular velocity
-    angular_vel.0 = Vec3::new(
+    angular_vel.0 = control_rotation(&plane_state);
+}
+
+// Angle of the nose above the horizon in radians, negative below it. Measured from the
+// forward vector, so it reads the same whichever way the plane is heading.
+pub fn climb_angle(transform: &Transform) -> f32 {
+    transform.forward().y.clamp(-1.0, 1.0).asin()
+}
+
+// Fast enough, with the nose far enough up, for the takeoff push off the water
+pub fn takeoff_ready(plane_state: &PlaneState, flight_config: &FlightConfig, transform: &Transform) -> bool {
+    plane_state.speed > flight_config.max_airspeed * flight_config.takeoff_speed_threshold
+        && climb_angle(transform) > TAKEOFF_MIN_PITCH
+}
+
+// Angular velocity the controls ask for: the turn momentum's pitch and yaw, and a roll
+// rate following the bank angle
+fn control_rotation(plane_state: &PlaneState) -> Vec3 {
+    Vec3::new(
         plane_state.turn_momentum.x,
         plane_state.turn_momentum.y,
         plane_state.bank_angle
-    ) * 5.0;
+    ) * 5.0
+}
+
+// Score a touchdown out of 100: a gentle sink rate earns most of the points, and
+// wings and nose held level the rest
+fn rate_landing(impact_velocity: f32, plane_transform: &Transform) -> f32 {
+    let bank = plane_transform.right().y.asin().abs();
+    let pitch = plane_transform.forward().y.asin().abs();
+    let penalty = |value: f32, limit: f32, weight: f32| (value / limit).min(1.0) * weight;
+
+    100.0
+        - penalty(impact_velocity, LANDING_MAX_SINK_RATE, LANDING_SINK_WEIGHT)
+        - penalty(bank, LANDING_MAX_BANK, LANDING_BANK_WEIGHT)
+        - penalty(pitch, LANDING_MAX_PITCH, LANDING_PITCH_WEIGHT)
+}
+
+// Per-frame factors such as the water damping were tuned at TUNING_FRAME_RATE. Raised to the
+// number of those frames `dt` spans, they decay by the same amount per second at any step.
+fn per_step(factor: f32, dt: f32) -> f32 {
+    factor.powf(dt * TUNING_FRAME_RATE)
+}
+
+// L engages the autopilot, holding the current altitude with the wings level
+pub fn toggle_autopilot(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut plane_state: ResMut<PlaneState>,
+    plane_query: Query<&Transform, With<Plane>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::KeyL) || plane_state.crashed {
+        return;
+    }
+    let Ok(plane_transform) = plane_query.get_single() else {
+        return;
+    };
+
+    plane_state.autopilot = !plane_state.autopilot;
+    plane_state.autopilot_altitude = plane_transform.translation.y;
+    info!("Autopilot {}", if plane_state.autopilot { "engaged" } else { "disengaged" });
+}
+
+// Pitch input that steers the nose toward the climb angle needed to get back to the
+// target altitude. The controller's pitch turns the nose about world X, so its effect
+// on the climb scales with (and flips with) how much the nose points along Z.
+fn autopilot_pitch(plane_transform: &Transform, target_altitude: f32) -> f32 {
+    let forward = plane_transform.forward();
+    let desired_climb = ((target_altitude - plane_transform.translation.y) * AUTOPILOT_ALTITUDE_GAIN)
+        .clamp(-AUTOPILOT_MAX_CLIMB, AUTOPILOT_MAX_CLIMB);
+    (-(desired_climb - forward.y) * AUTOPILOT_PITCH_GAIN * forward.z).clamp(-1.0, 1.0)
+}
+
+// M switches mouse flight on and off, capturing the cursor while it's on
+pub fn toggle_mouse_flight(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut mouse_flight: ResMut<MouseFlight>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::KeyM) {
+        return;
+    }
+
+    mouse_flight.enabled = !mouse_flight.enabled;
+    info!("Mouse flight {}", if mouse_flight.enabled { "on" } else { "off" });
+    for mut window in window_query.iter_mut() {
+        window.cursor_options.grab_mode = if mouse_flight.enabled {
+            CursorGrabMode::Locked
+        } else {
+            CursorGrabMode::None
+        };
+        window.cursor_options.visible = !mouse_flight.enabled;
+    }
+}
+
+// -1.0 while the negative key is held, 1.0 for the positive key
+fn key_axis(keyboard_input: &ButtonInput<KeyCode>, negative: KeyCode, positive: KeyCode) -> f32 {
+    if keyboard_input.pressed(negative) {
+        -1.0
+    } else if keyboard_input.pressed(positive) {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+// Stick deflection with the dead zone cut out
+fn stick_axis(gamepad: Option<&Gamepad>, axis: GamepadAxis) -> f32 {
+    let value = gamepad.and_then(|gamepad| gamepad.get(axis)).unwrap_or(0.0);
+    apply_dead_zone(value, GAMEPAD_DEAD_ZONE)
+}
+
+// Zero inside the dead zone, rescaled outside it so full deflection is still 1.0
+fn apply_dead_zone(value: f32, dead_zone: f32) -> f32 {
+    if value.abs() < dead_zone {
+        return 0.0;
+    }
+    value.signum() * (value.abs() - dead_zone) / (1.0 - dead_zone)
+}
+
+// Fraction of the bank left after leveling at `level_speed` for `dt`. Exponential decay
+// rather than `1 - level_speed * dt`, which overshoots past zero and flips the bank
+// whenever a slow frame makes level_speed * dt exceed 1.
+fn level_decay(level_speed: f32, dt: f32) -> f32 {
+    (-level_speed * dt).exp()
+}
+
+// Move a smoothed key axis toward the keys' raw -1, 0 or 1 at a steady rate, taking
+// `keyboard_ramp_time` to go from center to full deflection
+fn smooth_key_axis(current: f32, target: f32, flight_config: &FlightConfig, dt: f32) -> f32 {
+    if !flight_config.keyboard_smoothing || flight_config.keyboard_ramp_time <= 0.0 {
+        return target;
+    }
+    let max_step = dt / flight_config.keyboard_ramp_time;
+    current + (target - current).clamp(-max_step, max_step)
+}
+
+fn key_or_stick(key: f32, stick: f32) -> f32 {
+    if key != 0.0 { key } else { stick }
+}
+
+// Throttle lever movement: the throttle keys, or the right/left triggers pressed proportionally
+fn throttle_input(keyboard_input: &ButtonInput<KeyCode>, key_bindings: &KeyBindings, gamepad: Option<&Gamepad>) -> f32 {
+    let trigger = |button| {
+        let value = gamepad.and_then(|gamepad| gamepad.get(button)).unwrap_or(0.0);
+        if value < GAMEPAD_DEAD_ZONE { 0.0 } else { value }
+    };
+
+    key_or_stick(
+        key_axis(keyboard_input, key_bindings.throttle_down, key_bindings.throttle_up),
+        trigger(GamepadButton::RightTrigger2) - trigger(GamepadButton::LeftTrigger2),
+    )
 }
 
+// Toggle the auto-throttle with T and dial its setpoint with the throttle keys or triggers
+pub fn auto_throttle_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    mut auto_throttle: ResMut<AutoThrottle>,
+    plane_state: Res<PlaneState>,
+    flight_config: Res<FlightConfig>,
+    time: Res<Time>,
+    gamepads: Query<&Gamepad>,
+) {
+    if keyboard_input.just_pressed(KeyCode::KeyT) {
+        auto_throttle.enabled = !auto_throttle.enabled;
+        if auto_throttle.enabled {
+            // Start by holding whatever speed we're flying at
+            auto_throttle.target_speed = plane_state.momentum.length().clamp(flight_config.min_airspeed, flight_config.max_airspeed);
+        }
+        info!("Auto-throttle {}", if auto_throttle.enabled { "engaged" } else { "disengaged" });
+    }
+
+    if !auto_throttle.enabled {
+        return;
+    }
+
+    auto_throttle.target_speed += throttle_input(&keyboard_input, &key_bindings, gamepads.iter().next())
+        * AUTO_THROTTLE_SETPOINT_RATE
+        * time.delta_secs();
+    auto_throttle.target_speed = auto_throttle.target_speed.clamp(flight_config.min_airspeed, flight_config.max_airspeed);
+}
+
+// Move the throttle to hold the auto-throttle setpoint. Power goes up in a climb and
+// down in a dive before the airspeed drifts, then the remaining error is trimmed out.
+// The throttle moves no faster than the player could move it by hand.
+pub fn auto_throttle(
+    auto_throttle: Res<AutoThrottle>,
+    mut plane_state: ResMut<PlaneState>,
+    flight_config: Res<FlightConfig>,
+    fuel: Res<Fuel>,
+    time: Res<Time>,
+    plane_query: Query<&Transform, With<Plane>>,
+) {
+    // Boost takes over the power while it's on, and the speed eases off it afterwards
+    if !auto_throttle.enabled
+        || plane_state.crashed
+        || fuel.amount <= 0.0
+        || plane_state.boosting
+        || plane_state.speed > flight_config.max_airspeed
+    {
+        return;
+    }
+    let Ok(plane_transform) = plane_query.get_single() else {
+        return;
+    };
+
+    let target = auto_throttle.target_speed;
+    let airspeed = plane_state.momentum.length();
+    let climb = plane_transform.forward().y;
+
+    let commanded = (target
+        + target * climb * AUTO_THROTTLE_CLIMB_GAIN
+        + (target - airspeed) * AUTO_THROTTLE_GAIN)
+        .clamp(flight_config.min_airspeed, flight_config.max_airspeed);
+
+    let max_step = flight_config.acceleration * time.delta_secs();
+    let change = (commanded - plane_state.speed).clamp(-max_step, max_step);
+    plane_state.throttle = change / max_step.max(f32::EPSILON);
+    plane_state.speed += change;
+}
+
+// Fraction of full control response available at a given airspeed.
+// Scales with dynamic pressure (speed squared), reaching 1.0 at the control reference speed,
+// with a floor so the plane never becomes completely unresponsive.
+fn control_authority(speed: f32, flight_config: &FlightConfig) -> f32 {
+    (speed / flight_config.control_reference_speed)
+        .powi(2)
+        .clamp(flight_config.min_control_authority, 1.0)
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn plane_physics(
     mut plane_state: ResMut<PlaneState>,
+    flight_config: Res<FlightConfig>,
+    world_config: Res<WorldConfig>,
+    wind: Res<Wind>,
+    gravity: Res<Gravity>,
+    mut impact_events: EventWriter<WaterImpact>,
+    mut touchdown_events: EventWriter<Touchdown>,
+    mut camera_shake: ResMut<CameraShake>,
+    mut fuel: ResMut<Fuel>,
+    mut stats: ResMut<Stats>,
+    mut landing_rating: ResMut<LandingRating>,
     time: Res<Time>,
     mut plane_query: Query<(&mut Transform, &CollidingEntities, &mut LinearVelocity, &mut AngularVelocity), With<Plane>>,
     water_query: Query<Entity, With<Water>>,
 ) {
-    let (mut plane_transform, colliding_entities, mut linear_vel, mut angular_vel) = plane_query.single_mut();
+    let Ok((mut plane_transform, colliding_entities, mut linear_vel, mut angular_vel)) = plane_query.get_single_mut() else {
+        return;
+    };
     let dt = time.delta_secs();
-    let water_entity = water_query.single();
+
+    // The controller sets the rotation once a frame, but a frame can run several steps.
+    // Start every step from its rate so the terms added below don't pile up across them.
+    // A crashed plane has no controls and tumbles as the collisions left it.
+    if !plane_state.crashed {
+        angular_vel.0 = control_rotation(&plane_state);
+    }
+
+    // Burn fuel with the throttle. Once the tank runs dry the engine cuts and the
+    // airspeed bleeds away, leaving the plane to glide.
+    if !plane_state.crashed {
+        let throttle = (plane_state.speed / flight_config.max_airspeed).clamp(0.0, 1.0);
+        let burn = flight_config.fuel_idle_burn + flight_config.fuel_burn_rate * throttle;
+        fuel.amount = (fuel.amount - burn * dt).max(0.0);
+    }
+    if fuel.amount <= 0.0 {
+        plane_state.speed = (plane_state.speed - FUEL_OUT_SPEED_DECAY * dt).max(0.0);
+    }
 
     // Check if plane is touching water
-    let is_on_water = colliding_entities.contains(&water_entity);
+    let is_on_water = water_query
+        .get_single()
+        .is_ok_and(|water_entity| colliding_entities.contains(&water_entity));
     
     // Detect water impact (transition from air to water)
     let water_impact = is_on_water && !plane_state.was_on_water;
     
     if is_on_water {
-        // Ensure plane doesn't go below water line
-        if plane_transform.translation.y < 0.1 {
-            plane_transform.translation.y = 0.1;
-            
-            // Zero out any downward velocity to prevent sinking
-            if linear_vel.0.y < 0.0 {
-                linear_vel.0.y = 0.0;
+        match world_config.water_collider {
+            WaterCollider::Surface => {
+                // Float on the hull, measured from the wave surface so the plane rides the swells
+                let surface = water_surface_height(
+                    &world_config,
+                    plane_transform.translation.x,
+                    plane_transform.translation.z,
+                    time.elapsed_secs(),
+                );
+                plane_state.momentum.y = float_vertical_speed(
+                    plane_state.momentum.y,
+                    plane_transform.translation.y - surface,
+                    &flight_config,
+                    gravity.0.y,
+                    dt,
+                );
+            }
+            WaterCollider::Volume { .. } => {
+                // Push the plane back up in proportion to how deep it is
+                let depth = -plane_transform.translation.y;
+                if depth > 0.0 {
+                    plane_state.momentum.y += depth * flight_config.water_buoyancy * dt;
+
+                    // Water resists vertical motion so the plane surfaces without shooting out
+                    plane_state.momentum.y *= 1.0 - (flight_config.water_vertical_drag * dt).min(1.0);
+                }
             }
         }
         
@@ -137,22 +558,40 @@ pub fn plane_physics(
         if water_impact {
             // Check vertical velocity for impact effect
             let impact_velocity = linear_vel.0.y.abs();
+
+            if landing_rating.airborne_time > LANDING_MIN_AIRBORNE_TIME && !plane_state.crashed {
+                let rating = rate_landing(impact_velocity, &plane_transform);
+                landing_rating.record(rating);
+                touchdown_events.send(Touchdown {
+                    position: plane_transform.translation,
+                    rating,
+                });
+                info!("Landing rated {:.0}", rating);
+            }
+            landing_rating.airborne_time = 0.0;
             
-            if impact_velocity > WATER_IMPACT_THRESHOLD {
+            if impact_velocity > flight_config.water_impact_threshold {
+                impact_events.send(WaterImpact {
+                    position: plane_transform.translation,
+                    velocity: impact_velocity,
+                });
+                camera_shake.add_impact(impact_velocity, flight_config.camera_shake_max);
+
                 // Calculate bounce based on impact velocity
-                let bounce_force = impact_velocity * WATER_BOUNCE_FACTOR;
+                let bounce_force = impact_velocity * flight_config.water_bounce_factor;
                 plane_state.impact_bounce = bounce_force;
                 
                 // Apply additional slowdown on hard impact
-                plane_state.speed *= WATER_IMPACT_SLOWDOWN;
-                linear_vel.0 *= WATER_IMPACT_SLOWDOWN;
+                plane_state.speed *= flight_config.water_impact_slowdown;
+                plane_state.momentum *= flight_config.water_impact_slowdown;
             }
         }
         
-        // Apply bounce effect if active
+        // Apply bounce effect if active. Vertical pushes go into the momentum, since the
+        // velocity is set from it at the end of this system.
         if plane_state.impact_bounce > 0.0 {
-            linear_vel.0.y += plane_state.impact_bounce;
-            plane_state.impact_bounce *= 0.8; // Decay bounce effect
+            plane_state.momentum.y += plane_state.impact_bounce * dt * TUNING_FRAME_RATE;
+            plane_state.impact_bounce *= per_step(0.8, dt); // Decay bounce effect
             
             // Clear bounce when it gets small enough
             if plane_state.impact_bounce < 0.1 {
@@ -171,122 +610,800 @@ pub fn plane_physics(
             // Smoothly interpolate toward the level rotation
             plane_transform.rotation = plane_transform.rotation.slerp(
                 target_rotation, 
-                WATER_LEVEL_ROTATION_SPEED * dt
+                1.0 - (-flight_config.water_level_rotation_speed * dt).exp()
             );
             
             // Zero out any rotational velocity to prevent twitching
             angular_vel.0 = Vec3::ZERO;
         }
         
-        // Apply stronger water resistance
-        linear_vel.0 *= WATER_DAMPING;
+        // Water resistance: the hull won't slide sideways, so momentum across the heading
+        // dies away fast, and the stabilizing damps it further to stop the twitching. Along
+        // the heading the water state below slows the plane instead. All of it goes into the
+        // momentum, since the velocity is set from it at the end of this system.
+        let heading = plane_transform.forward().with_y(0.0).normalize_or_zero();
+        let slip = plane_state.momentum.with_y(0.0) - heading * plane_state.momentum.dot(heading);
+        let slip_left = per_step(flight_config.water_damping, dt) * per_step(flight_config.water_stabilize_factor, dt);
+        plane_state.momentum -= slip * (1.0 - slip_left);
         
-        // Reduce twitching by stabilizing movement
-        linear_vel.0.x *= WATER_STABILIZE_FACTOR;
-        linear_vel.0.z *= WATER_STABILIZE_FACTOR;
-        
-        // Gradually slow down to a stop when on water
-        if !water_impact { // Don't apply this on the first frame of water contact
-            plane_state.speed *= WATER_STOP_SPEED;
-            
-            // If speed is below threshold, come to a complete stop
-            if plane_state.speed < WATER_STOP_THRESHOLD {
-                plane_state.speed = plane_state.speed * 0.95;
-                
-                // When very slow, switch to sailing mode
-                if plane_state.speed < 1.0 {
-                    // Allow the plane to sail at a very low speed
-                    plane_state.speed = WATER_SAILING_SPEED;
-                    
-                    // Keep a small forward momentum for sailing
-                    let forward = plane_transform.forward();
-                    linear_vel.0 = forward * WATER_SAILING_SPEED;
+        // Slow down on the water until the power comes back up. The touchdown frame
+        // keeps its speed; the state machine takes over from the next one.
+        if water_impact {
+            plane_state.water_state = WaterState::Landing;
+        } else {
+            plane_state.water_state = plane_state.water_state.next(
+                plane_state.speed,
+                plane_state.throttle,
+                flight_config.water_stop_threshold,
+            );
+            match plane_state.water_state {
+                WaterState::Landing => {
+                    plane_state.speed *= per_step(flight_config.water_stop_speed, dt);
+                }
+                WaterState::Sailing => {
+                    plane_state.speed *= per_step(flight_config.water_stop_speed * WATER_SAILING_DRAG, dt);
                 }
+                WaterState::Stopped => plane_state.speed = 0.0,
+                WaterState::TakingOff => {}
             }
         }
 
         // Improved takeoff mechanism
-        // Check if plane has enough speed and positive pitch (elevator up)
-        let (pitch, _, _) = plane_transform.rotation.to_euler(EulerRot::XYZ);
-        let has_takeoff_speed = plane_state.speed > MAX_AIRSPEED * TAKEOFF_SPEED_THRESHOLD;
-        let has_positive_pitch = pitch < -0.1; // Negative pitch means nose up in this coordinate system
-        
-        if has_takeoff_speed && has_positive_pitch {
+        // Check if plane has enough speed and the nose up (elevator up)
+        if takeoff_ready(&plane_state, &flight_config, &plane_transform) {
             // Calculate takeoff force based on speed and pitch
-            let pitch_factor = (-pitch).max(0.0).min(1.0); // Convert to positive factor
-            let speed_factor = (plane_state.speed / MAX_AIRSPEED).min(1.0);
+            let pitch_factor = climb_angle(&plane_transform).clamp(0.0, 1.0);
+            let speed_factor = (plane_state.speed / flight_config.max_airspeed).min(1.0);
             
             // Combine factors for final takeoff force
-            let takeoff_strength = pitch_factor * speed_factor * TAKEOFF_FORCE;
+            let takeoff_strength = pitch_factor * speed_factor * flight_config.takeoff_force;
             let up_force = Vec3::Y * takeoff_strength * 2.0;
             
             // Apply upward force
-            linear_vel.0 += up_force * dt;
+            plane_state.momentum += up_force * dt;
             
             // If we're applying enough force, allow rotation again
             if takeoff_strength > 0.5 {
                 // Gradually restore control as we lift off
-                angular_vel.0 = Vec3::new(
-                    plane_state.turn_momentum.x,
-                    plane_state.turn_momentum.y,
-                    plane_state.bank_angle
-                ) * 5.0 * takeoff_strength;
+                angular_vel.0 = control_rotation(&plane_state) * takeoff_strength;
             }
         }
     }
 
     // Update was_on_water state for next frame
     plane_state.was_on_water = is_on_water;
-    
-    // Get current rotation as Euler angles for debug info
-    let (pitch, _, _) = plane_transform.rotation.to_euler(EulerRot::XYZ);
-    
-    // Print debug info periodically (approximately once per second)
-    if (time.elapsed_secs() * 1.0).floor() != (time.elapsed_secs() * 1.0 - dt).floor() {
-        print_debug_info(&plane_state, &plane_transform, is_on_water, pitch);
+    if !is_on_water {
+        landing_rating.airborne_time += dt;
     }
-
+    
     // Get the plane's forward direction
     let forward = plane_transform.forward();
 
+    if !is_on_water {
+        // Climbing costs airspeed and diving gives it back
+        // A dive can't push past the top speed, though boosted speed above it isn't cut
+        let speed_cap = flight_config.max_airspeed.max(plane_state.speed);
+        plane_state.speed -= forward.y * flight_config.climb_speed_loss * dt;
+        plane_state.speed = plane_state.speed.clamp(0.0, speed_cap);
+    }
+
+    // The air moves with the wind while airborne, so the plane drifts with it
+    let wind_velocity = if is_on_water {
+        Vec3::ZERO
+    } else {
+        wind.velocity_at(plane_transform.translation.y)
+    };
+
     // Update momentum with current direction and speed
-    let target_momentum = forward * plane_state.speed;
-    plane_state.momentum = plane_state.momentum.lerp(target_momentum, 1.0 - MOMENTUM);
+    let target_momentum = forward * plane_state.speed + wind_velocity;
+    plane_state.momentum = plane_state.momentum.lerp(target_momentum, smoothing(flight_config.momentum_response_time, dt));
+
+    // Stall when the airflow over the wings gets too slow, and stay stalled
+    // until it's comfortably back above the limit so the state doesn't flicker
+    let airflow = plane_state.momentum - wind_velocity;
+    let forward_airspeed = airflow.dot(*forward);
+    plane_state.stalling = if is_on_water {
+        false
+    } else if plane_state.stalling {
+        forward_airspeed < flight_config.stall_speed * flight_config.stall_recovery_factor
+    } else {
+        forward_airspeed < flight_config.stall_speed
+    };
+
+    if plane_state.stalling {
+        // Drop the nose and stop climbing; with no lift gravity takes over below.
+        // The nose-down rate goes on top of this step's control rate rather than
+        // building up, so the drop is the same however many steps a frame runs. A
+        // crashed plane's rotation isn't reset each step, so it's left to tumble.
+        if !plane_state.crashed {
+            angular_vel.0 -= plane_transform.right() * flight_config.stall_pitch_rate;
+        }
+        plane_state.momentum.y = plane_state.momentum.y.min(0.0);
+    }
+
+    // Velocity is driven from momentum, so the airborne forces are folded into it
+    if !is_on_water {
+        let lift = if plane_state.stalling {
+            Vec3::ZERO
+        } else {
+            compute_lift(
+                *forward,
+                *plane_transform.up(),
+                airflow,
+                flight_config.lift_coefficient,
+                flight_config.wing_incidence,
+            )
+        };
+        plane_state.momentum += (lift + gravity.0) * dt;
+    }
+
+    // Ground effect: skimming just above the water the wings get extra lift, growing
+    // inversely with height from nothing at the top of the band
+    let altitude = plane_transform.translation.y;
+    plane_state.in_ground_effect = !is_on_water
+        && !plane_state.stalling
+        && (flight_config.ground_effect_min_height..flight_config.ground_effect_max_height).contains(&altitude);
+    if plane_state.in_ground_effect {
+        let inverse_height = |height: f32| 1.0 / height;
+        let strength = (inverse_height(altitude) - inverse_height(flight_config.ground_effect_max_height))
+            / (inverse_height(flight_config.ground_effect_min_height) - inverse_height(flight_config.ground_effect_max_height));
+        let airspeed_factor = (forward_airspeed / flight_config.max_airspeed).clamp(0.0, 1.0);
+        plane_state.momentum.y += flight_config.ground_effect_lift * strength * airspeed_factor * dt;
+    }
 
     // Apply momentum to velocity
     linear_vel.0 = plane_state.momentum;
 
-    // Keep plane within bounds
-    let max_distance = WATER_SIZE * 0.8;
-    if plane_transform.translation.length() > max_distance {
-        linear_vel.0 = Vec3::new(0.0, 0.0, -MIN_AIRSPEED);
-        angular_vel.0 = Vec3::ZERO;
-        plane_state.momentum = Vec3::new(0.0, 0.0, -MIN_AIRSPEED);
-        plane_state.turn_momentum = Vec3::ZERO;
-        plane_state.bank_angle = 0.0;
-        plane_state.speed = MIN_AIRSPEED;
+    // Track the bests; a crashed plane's tumble doesn't count
+    if !plane_state.crashed {
+        let (altitude, speed) = (plane_transform.translation.y, plane_state.speed);
+        stats.session.update(altitude, speed);
+        stats.all_time.update(altitude, speed);
+    }
+
+    // Diving past the safe depth counts as a crash
+    let too_deep = matches!(world_config.water_collider, WaterCollider::Volume { .. })
+        && -plane_transform.translation.y > flight_config.water_max_safe_depth;
+    if too_deep {
+        warn!("Dove deeper than {:.0} m, resetting to the start", flight_config.water_max_safe_depth);
+    }
+
+    // Turn back toward the center near the edge of the play area, gently at first and
+    // harder the further out the plane gets, so it curves home without losing its speed.
+    // Facing straight out it picks a side rather than stalling on a zero turn. The yaw
+    // goes on top of this step's control rate, so it doesn't build up over a frame's steps.
+    let position = plane_transform.translation;
+    let boundary_pull = (world_config.distance_past_edge(position) / flight_config.boundary_margin + 1.0).max(0.0);
+    plane_state.returning_to_area = boundary_pull > 0.0 && !plane_state.crashed;
+    if plane_state.returning_to_area {
+        let heading = forward.with_y(0.0).normalize_or_zero();
+        let to_center = (-position).with_y(0.0).normalize_or_zero();
+        let turn = heading.cross(to_center).y;
+        let turn = if heading.dot(to_center) < 0.0 { turn.signum() } else { turn };
+        angular_vel.0 += Vec3::Y * turn * boundary_pull * flight_config.boundary_turn_rate;
+    }
+
+    // Past the soft edge, or too high, the plane goes back to the start
+    if too_deep || world_config.is_out_of_bounds(position, flight_config.boundary_reset_distance) {
+        reset_plane(&mut plane_state, &mut fuel, &flight_config, &mut plane_transform, &mut linear_vel, &mut angular_vel);
     }
 }
 
-// Print debug info to console
-fn print_debug_info(
-    plane_state: &PlaneState,
-    transform: &Transform,
-    is_on_water: bool,
-    pitch: f32,
+// Thermal lift, strongest at the center of a column and fading to nothing at its edge
+// and toward its top, so flying in or out of one never cuts the lift off at once.
+// Added to the momentum because plane_physics drives the velocity from it every frame.
+pub fn thermal_lift(
+    mut plane_state: ResMut<PlaneState>,
+    time: Res<Time>,
+    plane_query: Query<(&Transform, &CollidingEntities), With<Plane>>,
+    water_query: Query<Entity, With<Water>>,
+    thermal_query: Query<&Thermal>,
 ) {
-    // Check takeoff conditions
-    let has_takeoff_speed = plane_state.speed > MAX_AIRSPEED * TAKEOFF_SPEED_THRESHOLD;
-    let has_positive_pitch = pitch < -0.1;
-    let takeoff_ready = has_takeoff_speed && has_positive_pitch;
-    
-    println!(
-        "Airspeed: {:.1} ({:.0}%), Altitude: {:.1}, Pitch: {:.1}°, Status: {}, Takeoff Ready: {}",
-        plane_state.speed,
-        (plane_state.speed / MAX_AIRSPEED) * 100.0,
-        transform.translation.y,
-        pitch.to_degrees(),
-        if is_on_water { "ON WATER" } else { "AIRBORNE" },
-        if takeoff_ready { "YES" } else { "NO" }
-    );
+    let Ok((plane_transform, colliding_entities)) = plane_query.get_single() else {
+        return;
+    };
+    let is_on_water = water_query
+        .get_single()
+        .is_ok_and(|water_entity| colliding_entities.contains(&water_entity));
+    let position = plane_transform.translation;
+    if is_on_water || position.y > THERMAL_TOP_ALTITUDE {
+        return;
+    }
+
+    let lift: f32 = thermal_query
+        .iter()
+        .map(|thermal| {
+            let distance = thermal.position.with_y(0.0).distance(position.with_y(0.0));
+            let falloff = (1.0 - distance / thermal.radius).max(0.0);
+            thermal.strength * falloff * falloff
+        })
+        .sum();
+    let top_fade = ((THERMAL_TOP_ALTITUDE - position.y) / THERMAL_FADE_HEIGHT).min(1.0);
+    plane_state.momentum.y += lift * top_fade * time.delta_secs();
+}
+
+// G-force felt by the pilot: the plane's acceleration minus gravity, along its up axis.
+// Measured from the change in momentum between steps, which the velocity is set from.
+// A reset clears the previous momentum, so the teleport isn't read as a load.
+// Holding past BLACKOUT_G or REDOUT_G builds up strain that drains once the load eases.
+pub fn measure_g_force(
+    mut plane_state: ResMut<PlaneState>,
+    gravity: Res<Gravity>,
+    time: Res<Time>,
+    plane_query: Query<&Transform, With<Plane>>,
+) {
+    let Ok(plane_transform) = plane_query.get_single() else {
+        return;
+    };
+    let dt = time.delta_secs();
+    let momentum = plane_state.momentum;
+    let Some(previous) = plane_state.g_previous_momentum.replace(momentum).filter(|_| dt > 0.0) else {
+        return;
+    };
+
+    let load = (momentum - previous) / dt - gravity.0;
+    // Clamped so a hard jolt, like a bounce off an island, doesn't read as an enormous load
+    let g_force = (load.dot(*plane_transform.up()) / STANDARD_GRAVITY).clamp(-G_FORCE_LIMIT, G_FORCE_LIMIT);
+    plane_state.g_force += (g_force - plane_state.g_force) * smoothing(G_FORCE_SMOOTHING_TIME, dt);
+
+    let g_force = plane_state.g_force;
+    plane_state.g_strain = if g_force > BLACKOUT_G {
+        plane_state.g_strain + (g_force - BLACKOUT_G) * G_STRAIN_RATE * dt
+    } else if g_force < REDOUT_G {
+        plane_state.g_strain - (REDOUT_G - g_force) * G_STRAIN_RATE * dt
+    } else {
+        plane_state.g_strain.signum() * (plane_state.g_strain.abs() - G_STRAIN_RECOVERY * dt).max(0.0)
+    }
+    .clamp(-1.0, 1.0);
+}
+
+// F9 turns the turbulence on and off
+pub fn toggle_turbulence(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut turbulence: ResMut<Turbulence>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::F9) {
+        return;
+    }
+
+    turbulence.enabled = !turbulence.enabled;
+    info!("Turbulence {}", if turbulence.enabled { "on" } else { "off" });
+}
+
+// Small random bumps skimming the water and flying through clouds, stronger the lower
+// or deeper in the plane is and the faster it goes. Runs after plane_physics, which
+// resets the angular velocity to the control rate at the start of every step, so the
+// angular jitter lasts only this step instead of adding up over a frame's steps.
+#[allow(clippy::too_many_arguments)]
+pub fn turbulence(
+    turbulence: Res<Turbulence>,
+    mut plane_state: ResMut<PlaneState>,
+    flight_config: Res<FlightConfig>,
+    mut game_rng: ResMut<GameRng>,
+    time: Res<Time>,
+    mut plane_query: Query<(&Transform, &CollidingEntities, &mut AngularVelocity), With<Plane>>,
+    water_query: Query<Entity, With<Water>>,
+    cloud_query: Query<&Transform, (With<Cloud>, Without<Plane>)>,
+) {
+    let Ok((plane_transform, colliding_entities, mut angular_vel)) = plane_query.get_single_mut() else {
+        return;
+    };
+    let is_on_water = water_query
+        .get_single()
+        .is_ok_and(|water_entity| colliding_entities.contains(&water_entity));
+    if !turbulence.enabled || is_on_water || plane_state.crashed {
+        return;
+    }
+
+    let position = plane_transform.translation;
+    let near_water = (1.0 - position.y / TURBULENCE_WATER_HEIGHT).clamp(0.0, 1.0);
+    let in_cloud = cloud_query
+        .iter()
+        .map(|cloud_transform| {
+            // Distance in half-sizes, so 1.0 is roughly the cloud's surface
+            let offset = (position - cloud_transform.translation) / (cloud_transform.scale * 0.5).max(Vec3::splat(0.01));
+            (1.0 - offset.length() / TURBULENCE_CLOUD_REACH).max(0.0)
+        })
+        .fold(0.0, f32::max);
+    let strength = near_water.max(in_cloud) * (plane_state.speed / flight_config.max_airspeed).clamp(0.0, 1.0);
+    if strength <= 0.0 {
+        return;
+    }
+
+    let rng = game_rng.rng();
+    let mut jitter = || Vec3::new(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0));
+    angular_vel.0 += jitter() * TURBULENCE_ANGULAR_JITTER * strength;
+    plane_state.momentum += jitter() * TURBULENCE_MOMENTUM_JITTER * strength * time.delta_secs();
+}
+
+// Damage the plane when it hits an island, from the collision's first frame.
+// Impact speed comes from the plane's momentum, since the solver has already bounced
+// LinearVelocity by the time the event is read. Running out of health crashes the plane:
+// the engine cuts and the controls stop responding until it's reset.
+pub fn island_collisions(
+    mut collisions: EventReader<CollisionStarted>,
+    mut plane_state: ResMut<PlaneState>,
+    mut camera_shake: ResMut<CameraShake>,
+    flight_config: Res<FlightConfig>,
+    plane_query: Query<Entity, With<Plane>>,
+    island_query: Query<(), With<Island>>,
+) {
+    let Ok(plane_entity) = plane_query.get_single() else {
+        collisions.clear();
+        return;
+    };
+
+    for CollisionStarted(first, second) in collisions.read() {
+        let other = if *first == plane_entity {
+            *second
+        } else if *second == plane_entity {
+            *first
+        } else {
+            continue;
+        };
+        if !island_query.contains(other) || plane_state.crashed {
+            continue;
+        }
+
+        let impact_speed = plane_state.momentum.length();
+        if impact_speed < ISLAND_IMPACT_MIN_SPEED {
+            continue;
+        }
+
+        camera_shake.add_impact(impact_speed, flight_config.camera_shake_max);
+        let damage = (impact_speed - ISLAND_IMPACT_MIN_SPEED) * ISLAND_DAMAGE_PER_SPEED;
+        plane_state.health = (plane_state.health - damage).max(0.0);
+        info!("Hit an island at {:.1}, health {:.0}", impact_speed, plane_state.health);
+
+        if plane_state.health <= 0.0 {
+            plane_state.crashed = true;
+            plane_state.speed = 0.0;
+            warn!("Crashed! Press R to reset");
+        }
+    }
+}
+
+// Slowly fill the tank while the plane sits on the water near an island
+pub fn refuel(
+    mut fuel: ResMut<Fuel>,
+    flight_config: Res<FlightConfig>,
+    time: Res<Time>,
+    plane_query: Query<(&Transform, &CollidingEntities), With<Plane>>,
+    water_query: Query<Entity, With<Water>>,
+    island_query: Query<&Transform, With<Island>>,
+) {
+    if fuel.amount >= flight_config.fuel_capacity {
+        return;
+    }
+    let Ok((plane_transform, colliding_entities)) = plane_query.get_single() else {
+        return;
+    };
+    let is_on_water = water_query
+        .get_single()
+        .is_ok_and(|water_entity| colliding_entities.contains(&water_entity));
+    if !is_on_water {
+        return;
+    }
+
+    let plane_position = plane_transform.translation.with_y(0.0);
+    let near_island = island_query.iter().any(|island_transform| {
+        island_transform.translation.with_y(0.0).distance(plane_position) < REFUEL_RADIUS
+    });
+    if near_island {
+        fuel.amount = (fuel.amount + flight_config.refuel_rate * time.delta_secs()).min(flight_config.fuel_capacity);
+    }
+}
+
+// Pose the plane starts in: above the water at the center, facing +Z
+pub fn plane_spawn_transform() -> Transform {
+    Transform::from_xyz(0.0, 20.0, 0.0)
+        .with_rotation(Quat::from_rotation_y(PI))
+        .with_scale(Vec3::splat(PLANE_SCALE))
+}
+
+// Put the plane back at the start in level flight at minimum airspeed, clearing any
+// stall so control comes straight back
+pub fn reset_plane(
+    plane_state: &mut PlaneState,
+    fuel: &mut Fuel,
+    flight_config: &FlightConfig,
+    transform: &mut Transform,
+    linear_vel: &mut LinearVelocity,
+    angular_vel: &mut AngularVelocity,
+) {
+    *transform = plane_spawn_transform();
+    linear_vel.0 = Vec3::new(0.0, 0.0, -flight_config.min_airspeed);
+    angular_vel.0 = Vec3::ZERO;
+    plane_state.momentum = Vec3::new(0.0, 0.0, -flight_config.min_airspeed);
+    plane_state.turn_momentum = Vec3::ZERO;
+    plane_state.bank_angle = 0.0;
+    plane_state.speed = flight_config.min_airspeed;
+    plane_state.impact_bounce = 0.0;
+    plane_state.was_on_water = false;
+    plane_state.stalling = false;
+    plane_state.health = MAX_HEALTH;
+    plane_state.crashed = false;
+    plane_state.trim_pitch = 0.0;
+    plane_state.autopilot = false;
+    plane_state.boost_energy = 1.0;
+    plane_state.boosting = false;
+    plane_state.throttle = 0.0;
+    plane_state.water_state = WaterState::Landing;
+    plane_state.g_force = 1.0;
+    plane_state.g_strain = 0.0;
+    plane_state.g_previous_momentum = None;
+    plane_state.in_ground_effect = false;
+    plane_state.returning_to_area = false;
+    fuel.amount = flight_config.fuel_capacity;
+}
+
+// R puts the plane back at the start, for when it's stuck or upside down
+pub fn reset_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut plane_state: ResMut<PlaneState>,
+    mut fuel: ResMut<Fuel>,
+    flight_config: Res<FlightConfig>,
+    mut plane_query: Query<(&mut Transform, &mut LinearVelocity, &mut AngularVelocity), With<Plane>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::KeyR) {
+        return;
+    }
+    let Ok((mut transform, mut linear_vel, mut angular_vel)) = plane_query.get_single_mut() else {
+        return;
+    };
+
+    reset_plane(&mut plane_state, &mut fuel, &flight_config, &mut transform, &mut linear_vel, &mut angular_vel);
+    info!("Plane reset to the start");
+}
+
+// Spin the propeller with the throttle, idling slowly on the water
+pub fn spin_propeller(
+    plane_state: Res<PlaneState>,
+    time: Res<Time>,
+    mut propeller_query: Query<&mut Transform, With<Propeller>>,
+) {
+    let spin_rate = (plane_state.speed * PROPELLER_SPIN_PER_SPEED).max(PROPELLER_IDLE_SPIN);
+    for mut transform in propeller_query.iter_mut() {
+        transform.rotate_local_z(spin_rate * time.delta_secs());
+    }
+}
+
+// Vertical speed after `dt` afloat with the plane's origin `height` above the water
+// surface: buoyancy grows with how deep the hull sits, and damping settles the bobbing
+fn float_vertical_speed(vertical_speed: f32, height: f32, flight_config: &FlightConfig, gravity: f32, dt: f32) -> f32 {
+    let submersion = (PLANE_HULL_DEPTH - height).max(0.0);
+    let vertical_speed = vertical_speed + (submersion * flight_config.water_float_buoyancy + gravity) * dt;
+    vertical_speed * (-flight_config.water_float_damping * dt).exp()
+}
+
+// Lift from the wings, along the plane's up axis. Grows with the square of the airspeed
+// over the wings and with the angle of attack, plus the wing's built-in incidence so
+// level flight still lifts. Never pushes down.
+fn compute_lift(
+    forward: Vec3,
+    up: Vec3,
+    velocity: Vec3,
+    lift_coefficient: f32,
+    wing_incidence: f32,
+) -> Vec3 {
+    let airspeed = velocity.dot(forward).max(0.0);
+    if airspeed <= 0.0 {
+        return Vec3::ZERO;
+    }
+
+    // Positive when the air comes from below the nose
+    let angle_of_attack = (-velocity.dot(up)).atan2(airspeed);
+    let lift = lift_coefficient * airspeed * airspeed * (wing_incidence + angle_of_attack);
+    up * lift.max(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::time::TimeUpdateStrategy;
+    use crate::debug::restore_gravity;
+    use crate::resources::ZeroG;
+    use std::time::Duration;
+
+    // Fixed steps run so far, counted by integrate
+    #[derive(Resource, Default)]
+    struct Steps(u32);
+
+    // Stands in for the physics engine: moves and turns the plane by the velocities the
+    // flight systems left it, without any collisions
+    fn integrate(
+        mut steps: ResMut<Steps>,
+        time: Res<Time>,
+        mut plane_query: Query<(&mut Transform, &LinearVelocity, &AngularVelocity), With<Plane>>,
+    ) {
+        steps.0 += 1;
+        let dt = time.delta_secs();
+        for (mut transform, linear_vel, angular_vel) in &mut plane_query {
+            transform.translation += linear_vel.0 * dt;
+            transform.rotation = (Quat::from_scaled_axis(angular_vel.0 * dt) * transform.rotation).normalize();
+        }
+    }
+
+    // Headless app running the controller and the fixed flight steps the way FlightPlugin
+    // does, with every frame lasting `frame`. No plane is spawned.
+    fn flight_app(frame: Duration) -> App {
+        let flight_config = FlightConfig::default();
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(frame))
+            .insert_resource(PlaneState::new(&flight_config))
+            .insert_resource(Fuel { amount: flight_config.fuel_capacity })
+            .insert_resource(flight_config)
+            .insert_resource(WorldConfig::with_size(WATER_SIZE))
+            .insert_resource(GameRng::new(1))
+            .insert_resource(Gravity::default())
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<KeyBindings>()
+            .init_resource::<AutoThrottle>()
+            .init_resource::<MouseFlight>()
+            .init_resource::<KeyboardAxes>()
+            .init_resource::<Turbulence>()
+            .init_resource::<Stats>()
+            .init_resource::<LandingRating>()
+            .init_resource::<CameraShake>()
+            .init_resource::<Wind>()
+            .init_resource::<ZeroG>()
+            .init_resource::<Steps>()
+            .add_event::<WaterImpact>()
+            .add_event::<Touchdown>()
+            .add_event::<MouseMotion>()
+            .add_event::<CollisionStarted>()
+            .add_systems(RunFixedMainLoop, plane_controller.in_set(RunFixedMainLoopSystem::BeforeFixedMainLoop))
+            .add_systems(FixedUpdate, (
+                restore_gravity,
+                auto_throttle,
+                plane_physics,
+                thermal_lift,
+                turbulence,
+                measure_g_force,
+                island_collisions,
+                refuel,
+                integrate,
+            ).chain());
+        // The first update only starts the clock
+        app.update();
+        app
+    }
+
+    // Spawns the plane at `transform` flying at `speed`, touching the water if `water` is given
+    fn spawn_plane(app: &mut App, transform: Transform, speed: f32, water: Option<Entity>) {
+        let momentum = transform.forward() * speed;
+        let mut plane_state = app.world_mut().resource_mut::<PlaneState>();
+        plane_state.speed = speed;
+        plane_state.momentum = momentum;
+        plane_state.was_on_water = water.is_some();
+        let mut colliding_entities = CollidingEntities::default();
+        colliding_entities.extend(water);
+        app.world_mut().spawn((
+            Plane,
+            transform,
+            colliding_entities,
+            LinearVelocity(momentum),
+            AngularVelocity::default(),
+        ));
+    }
+
+    fn plane_transform(app: &mut App) -> Transform {
+        let world = app.world_mut();
+        *world.query_filtered::<&Transform, With<Plane>>().single(world)
+    }
+
+    #[test]
+    fn nose_up_at_speed_lifts_off_the_water() {
+        let mut app = flight_app(Duration::from_secs_f32(1.0 / 64.0));
+        let water = app.world_mut().spawn(Water).id();
+        let max_airspeed = app.world().resource::<FlightConfig>().max_airspeed;
+        // Heading the other way from the spawn heading, so nothing leans on one direction
+        let transform = Transform::from_xyz(0.0, PLANE_HULL_DEPTH, 0.0)
+            .with_rotation(Quat::from_rotation_x(0.3));
+        spawn_plane(&mut app, transform, max_airspeed * 0.9, Some(water));
+
+        let start = plane_transform(&mut app).translation.y;
+        for _ in 0..64 {
+            app.update();
+        }
+        assert!(plane_transform(&mut app).translation.y > start + 1.0);
+    }
+
+    #[test]
+    fn gravity_eases_back_in_after_zero_g() {
+        let mut app = flight_app(Duration::from_secs_f32(1.0 / 64.0));
+        app.world_mut().resource_mut::<Wind>().strength = 0.0;
+        app.world_mut().resource_mut::<Turbulence>().enabled = false;
+        // Slower than the wings can carry the weight at, so with gravity it settles into a sink
+        spawn_plane(&mut app, plane_spawn_transform().with_translation(Vec3::new(0.0, 400.0, 0.0)), 30.0, None);
+        let normal = app.world().resource::<Gravity>().0;
+        app.world_mut().resource_mut::<Gravity>().0 = Vec3::ZERO;
+        *app.world_mut().resource_mut::<ZeroG>() = ZeroG { enabled: true, normal: Some(normal) };
+        for _ in 0..5 * 64 {
+            app.update();
+        }
+
+        // Trimmed out with no weight to carry, the plane shouldn't drop when gravity comes back
+        app.world_mut().resource_mut::<ZeroG>().enabled = false;
+        let mut vertical_speed = app.world().resource::<PlaneState>().momentum.y;
+        let mut peak_sink_acceleration: f32 = 0.0;
+        for _ in 0..(ZERO_G_RESTORE_TIME as usize + 2) * 64 {
+            app.update();
+            let next = app.world().resource::<PlaneState>().momentum.y;
+            peak_sink_acceleration = peak_sink_acceleration.max((vertical_speed - next) * 64.0);
+            vertical_speed = next;
+        }
+        assert!(peak_sink_acceleration < 0.25 * STANDARD_GRAVITY);
+        assert_eq!(app.world().resource::<Gravity>().0, normal);
+        assert_eq!(app.world().resource::<ZeroG>().normal, None);
+    }
+
+    #[test]
+    fn flight_systems_tick_without_a_plane() {
+        let mut app = flight_app(Duration::from_secs_f32(1.0 / 64.0));
+        app.world_mut().spawn(Water);
+        for _ in 0..10 {
+            app.update();
+        }
+        assert_eq!(app.world().resource::<Steps>().0, 10);
+    }
+
+    // Flies one second of level cruise in frames of `frame_steps` fixed steps each
+    fn cruise_for_a_second(frame_steps: u32) -> (Transform, Vec3, f32, u32) {
+        let mut app = flight_app(Duration::from_micros(15_625 * frame_steps as u64));
+        app.world_mut().spawn(Water);
+        let speed = app.world().resource::<FlightConfig>().max_airspeed * 0.6;
+        spawn_plane(&mut app, plane_spawn_transform().with_translation(Vec3::new(0.0, 100.0, 0.0)), speed, None);
+        for _ in 0..64 / frame_steps {
+            app.update();
+        }
+        let plane_state = app.world().resource::<PlaneState>();
+        let (momentum, speed) = (plane_state.momentum, plane_state.speed);
+        (plane_transform(&mut app), momentum, speed, app.world().resource::<Steps>().0)
+    }
+
+    #[test]
+    fn flight_ends_the_same_however_the_frames_split_the_steps() {
+        let one_step_frames = cruise_for_a_second(1);
+        assert_eq!(one_step_frames.3, 64);
+        assert!(one_step_frames.0.translation.xz().length() > one_step_frames.2 * 0.5);
+        assert_eq!(cruise_for_a_second(2), one_step_frames);
+        assert_eq!(cruise_for_a_second(4), one_step_frames);
+    }
+
+    #[test]
+    fn per_step_factor_is_the_same_however_the_time_is_split() {
+        for factor in [0.8, 0.95, 0.99] {
+            let whole = per_step(factor, 0.1);
+            assert!((per_step(factor, 0.05).powi(2) - whole).abs() < 1e-6);
+            assert!((per_step(factor, 0.025).powi(4) - whole).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn per_step_factor_matches_the_tuning_frame_rate() {
+        assert!((per_step(0.8, 1.0 / TUNING_FRAME_RATE) - 0.8).abs() < 1e-6);
+        assert_eq!(per_step(0.8, 0.0), 1.0);
+    }
+
+    // Lift on a level plane flying straight along -Z at `velocity`, with the default wing
+    fn level_lift(velocity: Vec3) -> Vec3 {
+        compute_lift(Vec3::NEG_Z, Vec3::Y, velocity, LIFT_COEFFICIENT, WING_INCIDENCE)
+    }
+
+    #[test]
+    fn no_lift_without_airflow_over_the_wings() {
+        assert_eq!(level_lift(Vec3::ZERO), Vec3::ZERO);
+        assert_eq!(level_lift(Vec3::new(0.0, -10.0, 0.0)), Vec3::ZERO);
+        // Flying backwards
+        assert_eq!(level_lift(Vec3::new(0.0, 0.0, 30.0)), Vec3::ZERO);
+    }
+
+    #[test]
+    fn level_lift_balances_gravity_near_trim_speed() {
+        let lift = level_lift(Vec3::new(0.0, 0.0, -40.4));
+        assert_eq!(lift.x, 0.0);
+        assert_eq!(lift.z, 0.0);
+        assert!((lift.y - STANDARD_GRAVITY).abs() < 0.05);
+        // At the stall speed the wings no longer hold the plane up
+        assert!(level_lift(Vec3::new(0.0, 0.0, -STALL_SPEED)).y < STANDARD_GRAVITY);
+    }
+
+    #[test]
+    fn lift_grows_with_angle_of_attack_and_never_pushes_down() {
+        let level = level_lift(Vec3::new(0.0, 0.0, -40.0)).y;
+        // Sinking puts the air under the nose
+        assert!(level_lift(Vec3::new(0.0, -5.0, -40.0)).y > level);
+        // Climbing past the wing's incidence leaves no lift, rather than negative lift
+        assert_eq!(level_lift(Vec3::new(0.0, 8.0, -40.0)), Vec3::ZERO);
+    }
+
+    #[test]
+    fn flight_keys_follow_the_bindings() {
+        let key_bindings = KeyBindings {
+            roll_left: KeyCode::ArrowLeft,
+            roll_right: KeyCode::ArrowRight,
+            throttle_up: KeyCode::KeyR,
+            throttle_down: KeyCode::KeyF,
+            ..default()
+        };
+        let mut keyboard_input = ButtonInput::<KeyCode>::default();
+
+        // The old defaults do nothing once the axis is rebound
+        keyboard_input.press(KeyCode::KeyA);
+        keyboard_input.press(KeyCode::ArrowUp);
+        assert_eq!(key_axis(&keyboard_input, key_bindings.roll_left, key_bindings.roll_right), 0.0);
+        assert_eq!(throttle_input(&keyboard_input, &key_bindings, None), 0.0);
+
+        keyboard_input.press(KeyCode::ArrowLeft);
+        keyboard_input.press(KeyCode::KeyR);
+        assert_eq!(key_axis(&keyboard_input, key_bindings.roll_left, key_bindings.roll_right), -1.0);
+        assert_eq!(throttle_input(&keyboard_input, &key_bindings, None), 1.0);
+
+        keyboard_input.release(KeyCode::ArrowLeft);
+        keyboard_input.press(KeyCode::ArrowRight);
+        assert_eq!(key_axis(&keyboard_input, key_bindings.roll_left, key_bindings.roll_right), 1.0);
+    }
+
+    #[test]
+    fn leveling_never_flips_the_bank() {
+        let flight_config = FlightConfig::default();
+        for level_speed in [flight_config.auto_level_speed * 1.6, flight_config.water_level_speed] {
+            for dt in [0.0, 1.0 / 60.0, 0.25, 2.0, 100.0] {
+                // Never negative, so the bank can shrink to zero but not cross it
+                assert!((0.0..=1.0).contains(&level_decay(level_speed, dt)));
+            }
+            // A slow frame levels off as far as the frames it stands in for
+            let whole = level_decay(level_speed, 0.1);
+            assert!((level_decay(level_speed, 0.05).powi(2) - whole).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn floating_settles_where_buoyancy_balances_gravity() {
+        let flight_config = FlightConfig::default();
+        let dt = 1.0 / 64.0;
+        // Dropped onto the water from a little above it
+        let mut height = 1.0;
+        let mut vertical_speed = -3.0;
+        for _ in 0..(20 * 64) {
+            vertical_speed = float_vertical_speed(vertical_speed, height, &flight_config, -STANDARD_GRAVITY, dt);
+            height += vertical_speed * dt;
+        }
+
+        let depth = STANDARD_GRAVITY / flight_config.water_float_buoyancy;
+        assert!((height - (PLANE_HULL_DEPTH - depth)).abs() < 0.01);
+        assert!(vertical_speed.abs() < 0.01);
+    }
+
+    #[test]
+    fn key_axis_ramps_up_over_the_ramp_time() {
+        let flight_config = FlightConfig::default();
+        let dt = flight_config.keyboard_ramp_time / 4.0;
+        let mut axis = 0.0;
+        for step in 1..=4 {
+            axis = smooth_key_axis(axis, 1.0, &flight_config, dt);
+            assert!((axis - step as f32 / 4.0).abs() < 1e-5);
+        }
+        // Full deflection holds once reached
+        assert_eq!(smooth_key_axis(1.0, 1.0, &flight_config, dt), 1.0);
+    }
+
+    #[test]
+    fn key_axis_returns_to_center_on_release() {
+        let flight_config = FlightConfig::default();
+        let dt = flight_config.keyboard_ramp_time / 4.0;
+        let axis = smooth_key_axis(1.0, 0.0, &flight_config, dt);
+        assert!((axis - 0.75).abs() < 1e-5);
+        // A long frame stops at center rather than swinging past it
+        assert_eq!(smooth_key_axis(axis, 0.0, &flight_config, 10.0), 0.0);
+    }
+
+    #[test]
+    fn key_axis_is_raw_with_smoothing_off() {
+        let flight_config = FlightConfig { keyboard_smoothing: false, ..default() };
+        assert_eq!(smooth_key_axis(0.0, 1.0, &flight_config, 1.0 / 60.0), 1.0);
+        assert_eq!(smooth_key_axis(1.0, -1.0, &flight_config, 1.0 / 60.0), -1.0);
+    }
 }
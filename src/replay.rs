@@ -0,0 +1,193 @@
+use bevy::prelude::*;
+use std::collections::VecDeque;
+use rand::rngs::StdRng;
+use crate::components::{Plane, GhostPlane};
+use crate::constants::RECORDER_MAX_DURATION;
+use crate::resources::{PlaneState, GameRng};
+use crate::setup::spawn_plane;
+
+// The plane's pose at one physics step
+pub struct FlightSample {
+    pub time: f32,
+    pub translation: Vec3,
+    pub rotation: Quat,
+    pub speed: f32,
+}
+
+// A ghost plane flying back through the recording. `time` is on the recording's clock.
+pub struct Playback {
+    pub ghost: Entity,
+    pub time: f32,
+    pub speed: f32,
+}
+
+// The last RECORDER_MAX_DURATION seconds of flight, oldest first. Nothing is recorded
+// until F7 turns the recorder on, so it costs nothing when unused.
+#[derive(Resource, Default)]
+pub struct FlightRecorder {
+    pub recording: bool,
+    pub samples: VecDeque<FlightSample>,
+    pub playback: Option<Playback>,
+    pub rng_snapshot: Option<StdRng>, // GameRng's state when the recording started
+}
+
+impl FlightRecorder {
+    pub fn duration(&self) -> f32 {
+        match (self.samples.front(), self.samples.back()) {
+            (Some(first), Some(last)) => last.time - first.time,
+            _ => 0.0,
+        }
+    }
+
+    // Pose at `time`, blended between the two samples either side of it
+    fn sample_at(&self, time: f32) -> Option<(Transform, f32)> {
+        let next = self.samples.partition_point(|sample| sample.time < time);
+        let after = self.samples.get(next)?;
+        let Some(before) = next.checked_sub(1).and_then(|index| self.samples.get(index)) else {
+            return Some((Transform::from_translation(after.translation).with_rotation(after.rotation), after.speed));
+        };
+
+        let t = ((time - before.time) / (after.time - before.time).max(f32::EPSILON)).clamp(0.0, 1.0);
+        let transform = Transform::from_translation(before.translation.lerp(after.translation, t))
+            .with_rotation(before.rotation.slerp(after.rotation, t));
+        Some((transform, before.speed.lerp(after.speed, t)))
+    }
+}
+
+// F7 starts a fresh recording or stops the current one. The random number generator's
+// state is kept with the recording so playback can draw the same values again.
+pub fn toggle_recording(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut recorder: ResMut<FlightRecorder>,
+    game_rng: Res<GameRng>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::F7) {
+        return;
+    }
+
+    recorder.recording = !recorder.recording;
+    if recorder.recording {
+        recorder.samples.clear();
+        recorder.rng_snapshot = Some(game_rng.snapshot());
+        info!("Flight recording started");
+    } else {
+        info!("Flight recording stopped ({:.0} s)", recorder.duration());
+    }
+}
+
+// Store the plane's pose every physics step, dropping whatever is older than the cap
+pub fn record_flight(
+    mut recorder: ResMut<FlightRecorder>,
+    plane_state: Res<PlaneState>,
+    time: Res<Time>,
+    plane_query: Query<&Transform, With<Plane>>,
+) {
+    if !recorder.recording {
+        return;
+    }
+    let Ok(plane_transform) = plane_query.get_single() else {
+        return;
+    };
+
+    let now = time.elapsed_secs();
+    recorder.samples.push_back(FlightSample {
+        time: now,
+        translation: plane_transform.translation,
+        rotation: plane_transform.rotation,
+        speed: plane_state.speed,
+    });
+    while recorder.samples.front().is_some_and(|sample| now - sample.time > RECORDER_MAX_DURATION) {
+        recorder.samples.pop_front();
+    }
+}
+
+// F8 replays the recording on a see-through ghost plane, which the chase camera follows,
+// and stops it early on a second press. Playing back ends any recording in progress, and
+// the autopilot is engaged so the real plane holds its altitude while out of view. The
+// effects during playback draw from a copy of the generator as it was when the recording
+// started, so they get the same values they did then, and the live stream resumes after.
+#[allow(clippy::too_many_arguments)]
+pub fn toggle_playback(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut recorder: ResMut<FlightRecorder>,
+    mut game_rng: ResMut<GameRng>,
+    mut plane_state: ResMut<PlaneState>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    plane_query: Query<&Transform, With<Plane>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::F8) {
+        return;
+    }
+
+    if let Some(playback) = recorder.playback.take() {
+        commands.entity(playback.ghost).despawn_recursive();
+        game_rng.end_playback();
+        info!("Replay stopped");
+        return;
+    }
+
+    let (Some(first), true) = (recorder.samples.front(), recorder.samples.len() > 1) else {
+        info!("Nothing recorded to replay (F7 records)");
+        return;
+    };
+    let start = Transform::from_translation(first.translation).with_rotation(first.rotation);
+    let (start_time, start_speed) = (first.time, first.speed);
+
+    let ghost_material = materials.add(StandardMaterial {
+        base_color: Color::srgba(0.6, 0.8, 1.0, 0.4),
+        alpha_mode: AlphaMode::Blend,
+        ..default()
+    });
+    let ghost = spawn_plane(&mut commands, &mut meshes, &mut materials, start, ghost_material);
+    commands.entity(ghost).insert((GhostPlane, Name::new("Ghost Plane")));
+
+    recorder.recording = false;
+    recorder.playback = Some(Playback { ghost, time: start_time, speed: start_speed });
+    if let Some(snapshot) = &recorder.rng_snapshot {
+        game_rng.start_playback(snapshot);
+    }
+    info!("Replaying {:.0} s of flight", recorder.duration());
+
+    if let Ok(plane_transform) = plane_query.get_single()
+        && !plane_state.autopilot
+        && !plane_state.crashed
+    {
+        plane_state.autopilot = true;
+        plane_state.autopilot_altitude = plane_transform.translation.y;
+    }
+}
+
+// Move the ghost along the recording in real time, removing it at the end
+pub fn play_back_flight(
+    mut commands: Commands,
+    mut recorder: ResMut<FlightRecorder>,
+    mut game_rng: ResMut<GameRng>,
+    time: Res<Time>,
+    mut ghost_query: Query<&mut Transform, With<GhostPlane>>,
+) {
+    let Some(playback_time) = recorder.playback.as_ref().map(|playback| playback.time + time.delta_secs()) else {
+        return;
+    };
+
+    match recorder.sample_at(playback_time) {
+        Some((transform, speed)) => {
+            let Some(playback) = recorder.playback.as_mut() else {
+                return;
+            };
+            playback.time = playback_time;
+            playback.speed = speed;
+            if let Ok(mut ghost_transform) = ghost_query.get_mut(playback.ghost) {
+                *ghost_transform = transform;
+            }
+        }
+        None => {
+            if let Some(playback) = recorder.playback.take() {
+                commands.entity(playback.ghost).despawn_recursive();
+                game_rng.end_playback();
+                info!("Replay finished");
+            }
+        }
+    }
+}
@@ -1,4 +1,7 @@
 use bevy::prelude::*;
+use rand::{SeedableRng, rngs::StdRng};
+use serde::{Deserialize, Serialize};
+use crate::constants::*;
 
 #[derive(Resource)]
 pub struct PlaneState {
@@ -8,4 +11,926 @@ pub struct PlaneState {
     pub bank_angle: f32,
     pub was_on_water: bool, // Track if the plane was on water in the previous frame
     pub impact_bounce: f32, // Track bounce effect after water impact
+    pub health: f32,
+    pub stalling: bool, // Airspeed too low to fly; lift is lost until it recovers
+    pub crashed: bool, // Health ran out; the engine and controls are dead until reset
+    pub trim_pitch: f32, // Pitch input held hands-off by the trim, -1 (nose down) to 1 (nose up)
+    pub autopilot: bool, // Wings leveler and altitude hold (L); any manual input disengages it
+    pub autopilot_altitude: f32, // Altitude the autopilot holds, taken when it was engaged
+    pub boost_energy: f32, // Boost meter, 0 (empty) to 1 (full)
+    pub boosting: bool, // Boost is held and has energy left
+    pub throttle: f32, // Power being added (positive) or taken off, from the throttle keys or the auto-throttle
+    pub water_state: WaterState, // What the plane is doing on the water; only meaningful while on it
+    pub g_force: f32, // Load factor along the plane's up axis, in G; 1 in level flight
+    pub g_strain: f32, // Sustained G effect on the pilot, -1 (full redout) to 1 (full blackout)
+    pub g_previous_momentum: Option<Vec3>, // Momentum at the last G measurement; none until the first step after a reset
+    pub in_ground_effect: bool, // Skimming low enough over the water to get the ground effect lift bonus
+    pub returning_to_area: bool, // Near or past the play area edge and being steered back
+}
+
+impl PlaneState {
+    // At rest state for a plane launching at the configured minimum airspeed
+    pub fn new(flight_config: &FlightConfig) -> Self {
+        Self {
+            speed: flight_config.min_airspeed,
+            momentum: Vec3::new(0.0, 0.0, -flight_config.min_airspeed),
+            turn_momentum: Vec3::ZERO,
+            bank_angle: 0.0,
+            was_on_water: false,
+            impact_bounce: 0.0,
+            health: MAX_HEALTH,
+            stalling: false,
+            crashed: false,
+            trim_pitch: 0.0,
+            autopilot: false,
+            autopilot_altitude: 0.0,
+            boost_energy: 1.0,
+            boosting: false,
+            throttle: 0.0,
+            water_state: WaterState::Landing,
+            g_force: 1.0,
+            g_strain: 0.0,
+            g_previous_momentum: None,
+            in_ground_effect: false,
+            returning_to_area: false,
+        }
+    }
+}
+
+// The plane's progress on the water. Touching down starts a Landing; plane_physics
+// advances the state every step and slows the plane according to it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum WaterState {
+    #[default]
+    Landing, // Water drag bleeds off the touchdown speed
+    Sailing, // Slow enough to coast, drifting to a stop
+    Stopped, // At rest, floating
+    TakingOff, // Power is up and the water no longer slows the plane
+}
+
+impl WaterState {
+    // Raising the power from any state starts a takeoff run; cutting it during one
+    // slows the plane down as after a landing
+    pub fn next(self, speed: f32, throttle: f32, stop_threshold: f32) -> Self {
+        if throttle > 0.0 {
+            return WaterState::TakingOff;
+        }
+        match self {
+            WaterState::Landing | WaterState::TakingOff if speed < stop_threshold => WaterState::Sailing,
+            WaterState::TakingOff => WaterState::Landing,
+            WaterState::Sailing if speed < WATER_STOPPED_SPEED => WaterState::Stopped,
+            state => state,
+        }
+    }
+}
+
+// Flight handling, read by the plane systems every frame. Defaults come from constants.rs
+// and can be overridden from config/flight.ron at startup; missing fields keep their defaults.
+// All fields can be changed while flying, with two caveats:
+// - `min_airspeed` also sets the launch speed, which only applies on the next start.
+// - `water_max_safe_depth` must stay shallower than the water volume depth chosen at startup.
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FlightConfig {
+    // Airspeed
+    pub min_airspeed: f32,
+    pub max_airspeed: f32,
+    pub acceleration: f32,
+
+    // Controls
+    pub turn_speed: f32,
+    pub pitch_sensitivity: f32,
+    pub base_roll_sensitivity: f32,
+    pub yaw_sensitivity: f32,
+    pub momentum_response_time: f32, // Seconds for the momentum to close ~63% of the gap to its target
+    pub turn_response_time: f32, // Same for the turn momentum
+    pub auto_level_speed: f32,
+    pub bank_turn_ratio: f32,
+    pub control_reference_speed: f32,
+    pub min_control_authority: f32,
+    pub mouse_sensitivity: f32,
+    pub keyboard_smoothing: bool, // Ramp the roll, pitch and yaw keys in and out; off gives the raw full deflection
+    pub keyboard_ramp_time: f32, // Seconds for a smoothed key axis to go from center to full deflection
+
+    // Lift and stall
+    pub climb_speed_loss: f32,
+    pub stall_speed: f32,
+    pub stall_recovery_factor: f32,
+    pub stall_pitch_rate: f32,
+    pub lift_coefficient: f32,
+    pub wing_incidence: f32,
+    pub ground_effect_min_height: f32,
+    pub ground_effect_max_height: f32,
+    pub ground_effect_lift: f32, // Extra upward acceleration at the minimum height and full airspeed
+
+    // Water
+    pub water_damping: f32,
+    pub water_level_speed: f32,
+    pub takeoff_speed_threshold: f32,
+    pub takeoff_force: f32,
+    pub water_impact_threshold: f32,
+    pub water_bounce_factor: f32,
+    pub water_impact_slowdown: f32,
+    pub water_stop_speed: f32,
+    pub water_stop_threshold: f32,
+    pub water_stabilize_factor: f32,
+    pub water_level_rotation_speed: f32,
+    pub water_buoyancy: f32,
+    pub water_vertical_drag: f32,
+    pub water_float_buoyancy: f32,
+    pub water_float_damping: f32,
+    pub water_max_safe_depth: f32,
+
+    // Fuel
+    pub fuel_capacity: f32,
+    pub fuel_burn_rate: f32,
+    pub fuel_idle_burn: f32,
+    pub refuel_rate: f32,
+
+    // Play area boundary
+    pub boundary_margin: f32, // Width of the band inside the edge where the plane is turned back
+    pub boundary_turn_rate: f32, // Yaw rate turning the plane back at the edge
+    pub boundary_reset_distance: f32, // Distance past the edge where the plane is reset after all
+
+    // Feedback
+    pub camera_shake_max: f32,
+
+    // Haze
+    pub fog_color: [f32; 3], // Daytime haze color (sRGB); dusk and night tint it like the sky
+    pub fog_visibility: f32, // Distance at which the haze all but hides things; lower is denser
+}
+
+impl Default for FlightConfig {
+    fn default() -> Self {
+        Self {
+            min_airspeed: MIN_AIRSPEED,
+            max_airspeed: MAX_AIRSPEED,
+            acceleration: ACCELERATION,
+            turn_speed: TURN_SPEED,
+            pitch_sensitivity: PITCH_SENSITIVITY,
+            base_roll_sensitivity: BASE_ROLL_SENSITIVITY,
+            yaw_sensitivity: YAW_SENSITIVITY,
+            momentum_response_time: MOMENTUM_RESPONSE_TIME,
+            turn_response_time: TURN_RESPONSE_TIME,
+            auto_level_speed: AUTO_LEVEL_SPEED,
+            bank_turn_ratio: BANK_TURN_RATIO,
+            control_reference_speed: CONTROL_REFERENCE_SPEED,
+            min_control_authority: MIN_CONTROL_AUTHORITY,
+            mouse_sensitivity: MOUSE_SENSITIVITY,
+            keyboard_smoothing: true,
+            keyboard_ramp_time: KEYBOARD_RAMP_TIME,
+            climb_speed_loss: CLIMB_SPEED_LOSS,
+            stall_speed: STALL_SPEED,
+            stall_recovery_factor: STALL_RECOVERY_FACTOR,
+            stall_pitch_rate: STALL_PITCH_RATE,
+            lift_coefficient: LIFT_COEFFICIENT,
+            wing_incidence: WING_INCIDENCE,
+            ground_effect_min_height: GROUND_EFFECT_MIN_HEIGHT,
+            ground_effect_max_height: GROUND_EFFECT_MAX_HEIGHT,
+            ground_effect_lift: GROUND_EFFECT_LIFT,
+            water_damping: WATER_DAMPING,
+            water_level_speed: WATER_LEVEL_SPEED,
+            takeoff_speed_threshold: TAKEOFF_SPEED_THRESHOLD,
+            takeoff_force: TAKEOFF_FORCE,
+            water_impact_threshold: WATER_IMPACT_THRESHOLD,
+            water_bounce_factor: WATER_BOUNCE_FACTOR,
+            water_impact_slowdown: WATER_IMPACT_SLOWDOWN,
+            water_stop_speed: WATER_STOP_SPEED,
+            water_stop_threshold: WATER_STOP_THRESHOLD,
+            water_stabilize_factor: WATER_STABILIZE_FACTOR,
+            water_level_rotation_speed: WATER_LEVEL_ROTATION_SPEED,
+            water_buoyancy: WATER_BUOYANCY,
+            water_vertical_drag: WATER_VERTICAL_DRAG,
+            water_float_buoyancy: WATER_FLOAT_BUOYANCY,
+            water_float_damping: WATER_FLOAT_DAMPING,
+            water_max_safe_depth: WATER_MAX_SAFE_DEPTH,
+            fuel_capacity: FUEL_CAPACITY,
+            fuel_burn_rate: FUEL_BURN_RATE,
+            fuel_idle_burn: FUEL_IDLE_BURN,
+            refuel_rate: REFUEL_RATE,
+            boundary_margin: BOUNDARY_MARGIN,
+            boundary_turn_rate: BOUNDARY_TURN_RATE,
+            boundary_reset_distance: BOUNDARY_RESET_DISTANCE,
+            camera_shake_max: CAMERA_SHAKE_MAX,
+            fog_color: FOG_COLOR,
+            fog_visibility: FOG_VISIBILITY,
+        }
+    }
+}
+
+// Wind over the world, varied slowly by update_wind. `direction` is the horizontal
+// unit vector the wind blows toward and `strength` its speed at altitude.
+#[derive(Resource)]
+pub struct Wind {
+    pub direction: Vec3,
+    pub strength: f32,
+}
+
+impl Default for Wind {
+    fn default() -> Self {
+        Self {
+            direction: Vec3::new(WIND_BASE_HEADING.sin(), 0.0, WIND_BASE_HEADING.cos()),
+            strength: WIND_MEAN_STRENGTH,
+        }
+    }
+}
+
+impl Wind {
+    // Wind velocity at an altitude; it dies away toward the water so landings stay fair
+    pub fn velocity_at(&self, altitude: f32) -> Vec3 {
+        let factor = (altitude / WIND_FULL_ALTITUDE).clamp(0.0, 1.0);
+        self.direction * self.strength * factor * factor * (3.0 - 2.0 * factor)
+    }
+}
+
+// Fuel left in the tank. Burned by plane_physics; with the tank dry the engine cuts.
+#[derive(Resource)]
+pub struct Fuel {
+    pub amount: f32,
+}
+
+// Rating out of 100 of the last water landing and the best this session. The HUD
+// shows the last one for `banner_time` seconds after touchdown.
+#[derive(Resource, Default)]
+pub struct LandingRating {
+    pub last: Option<f32>,
+    pub best: f32,
+    pub banner_time: f32,
+    pub airborne_time: f32, // Time since leaving the water, so brief skips aren't rated
+}
+
+impl LandingRating {
+    pub fn record(&mut self, rating: f32) {
+        self.last = Some(rating);
+        self.best = self.best.max(rating);
+        self.banner_time = LANDING_BANNER_TIME;
+    }
+}
+
+// Highest altitude and airspeed reached
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Records {
+    pub max_altitude: f32,
+    pub max_speed: f32,
+}
+
+impl Records {
+    pub fn update(&mut self, altitude: f32, speed: f32) {
+        self.max_altitude = self.max_altitude.max(altitude);
+        self.max_speed = self.max_speed.max(speed);
+    }
+}
+
+// Bests for this session and of all time. Only the all-time records are saved,
+// to STATS_FILE on exit.
+#[derive(Resource, Default)]
+pub struct Stats {
+    pub session: Records,
+    pub all_time: Records,
+}
+
+// Camera shake from hard impacts. The magnitude decays back to zero; `offset` is the
+// shake applied to the camera last frame, taken off again before the camera follows.
+#[derive(Resource, Default)]
+pub struct CameraShake {
+    pub magnitude: f32,
+    pub offset: Vec3,
+}
+
+impl CameraShake {
+    // Shake for an impact at the given speed, never weaker than a shake already running
+    pub fn add_impact(&mut self, impact_speed: f32, max_magnitude: f32) {
+        let magnitude = (impact_speed * CAMERA_SHAKE_PER_SPEED).min(max_magnitude);
+        self.magnitude = self.magnitude.max(magnitude);
+    }
+}
+
+#[derive(Resource)]
+pub struct QuitSettings {
+    pub exit_key: KeyCode,
+    pub confirm: bool, // Ask before quitting instead of exiting straight away
+}
+
+impl Default for QuitSettings {
+    fn default() -> Self {
+        Self {
+            exit_key: KeyCode::Escape,
+            confirm: true,
+        }
+    }
+}
+
+impl QuitSettings {
+    // Keys offered for `--exit-key`, all ones nothing else is bound to
+    pub fn exit_key_from_arg(arg: &str) -> Option<KeyCode> {
+        match arg {
+            "escape" => Some(KeyCode::Escape),
+            "backspace" => Some(KeyCode::Backspace),
+            "f10" => Some(KeyCode::F10),
+            "f11" => Some(KeyCode::F11),
+            "pause" => Some(KeyCode::Pause),
+            "end" => Some(KeyCode::End),
+            "delete" => Some(KeyCode::Delete),
+            _ => None,
+        }
+    }
+}
+
+// Keys for the flight controls; the defaults are the WASD/QE and arrow layout
+#[derive(Resource)]
+pub struct KeyBindings {
+    pub pitch_up: KeyCode,
+    pub pitch_down: KeyCode,
+    pub roll_left: KeyCode,
+    pub roll_right: KeyCode,
+    pub yaw_left: KeyCode,
+    pub yaw_right: KeyCode,
+    pub throttle_up: KeyCode,
+    pub throttle_down: KeyCode,
+    pub trim_up: KeyCode,
+    pub trim_down: KeyCode,
+    pub boost: KeyCode,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            pitch_up: KeyCode::KeyS,
+            pitch_down: KeyCode::KeyW,
+            roll_left: KeyCode::KeyA,
+            roll_right: KeyCode::KeyD,
+            yaw_left: KeyCode::KeyQ,
+            yaw_right: KeyCode::KeyE,
+            throttle_up: KeyCode::ArrowUp,
+            throttle_down: KeyCode::ArrowDown,
+            trim_up: KeyCode::PageUp,
+            trim_down: KeyCode::PageDown,
+            boost: KeyCode::ShiftLeft,
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct QuitDialog {
+    pub open: bool,
+    pub quit_selected: bool, // Which option Enter activates; opens on "keep flying"
+    pub exit_key_held: f32, // Seconds the exit key has been held, for force quit
+}
+
+// Fires once per DEBUG_PRINT_INTERVAL of game time for the flight debug log
+#[derive(Resource)]
+pub struct DebugPrintTimer(pub Timer);
+
+// Whether F12 screenshots keep the HUD; turned off with `--screenshot-no-hud`
+// for clean shots of the scenery
+#[derive(Resource)]
+pub struct ScreenshotSettings {
+    pub include_hud: bool,
+}
+
+impl Default for ScreenshotSettings {
+    fn default() -> Self {
+        Self { include_hud: true }
+    }
+}
+
+// Whether the on-screen debug overlay and flight debug log are on (F3)
+#[derive(Resource, Default)]
+pub struct DebugOverlay {
+    pub enabled: bool,
+}
+
+impl Default for DebugPrintTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(DEBUG_PRINT_INTERVAL, TimerMode::Repeating))
+    }
+}
+
+// World layout, read by `setup` when building the world. `with_size` keeps the sizes
+// and counts in step; they can be set apart afterwards.
+#[derive(Resource)]
+pub struct WorldConfig {
+    pub water_visual_size: f32, // Side length of the water surface and its collider
+    pub play_area_half_extent: f32, // Half width of the square play area, measured on X and Z
+    pub max_altitude: f32, // Altitude ceiling of the play area
+    pub spawn_radius: f32, // Half extent of the square clouds and islands are placed in
+    pub island_count: usize,
+    pub cloud_count: usize, // At high graphics quality; lower qualities spawn a fraction
+    pub endless: bool, // Endless ocean (--endless): islands and clouds stream in around the plane at the same density
+    pub water_collider: WaterCollider,
+    pub water_waves: bool, // Animated swells; off with --no-waves for low-end machines
+    pub billboard_clouds: bool, // Soft clusters of camera-facing puffs; off with --cuboid-clouds for the cheaper boxes
+    pub gradient_sky: bool, // Sky shaded from the horizon haze to a deeper zenith; off with --flat-sky for a single color
+    pub island_detail_distance: f32, // Past this distance from the plane islands swap to a low-detail mesh
+    pub island_draw_distance: f32, // Past this distance from the plane islands aren't drawn
+    pub cloud_draw_distance: f32, // Past this distance from the plane clouds aren't drawn
+}
+
+impl Default for WorldConfig {
+    fn default() -> Self {
+        Self::with_size(WATER_SIZE)
+    }
+}
+
+impl WorldConfig {
+    // A world `size` units across. Islands and clouds keep the default world's density,
+    // so their counts grow with the area.
+    pub fn with_size(size: f32) -> Self {
+        let area_scale = (size / WATER_SIZE).powi(2);
+        Self {
+            water_visual_size: size,
+            play_area_half_extent: size * 0.8,
+            max_altitude: MAX_ALTITUDE,
+            spawn_radius: size / 2.0,
+            island_count: (ISLAND_COUNT as f32 * area_scale).round() as usize,
+            cloud_count: (CLOUD_COUNT as f32 * area_scale).round() as usize,
+            endless: false,
+            water_collider: WaterCollider::Surface,
+            water_waves: true,
+            billboard_clouds: true,
+            gradient_sky: true,
+            island_detail_distance: ISLAND_DETAIL_DISTANCE,
+            island_draw_distance: ISLAND_DRAW_DISTANCE,
+            cloud_draw_distance: CLOUD_DRAW_DISTANCE,
+        }
+    }
+
+    // Horizontal distance past the play area's square edge, negative while inside it.
+    // The endless ocean has no edge.
+    pub fn distance_past_edge(&self, position: Vec3) -> f32 {
+        if self.endless {
+            return f32::NEG_INFINITY;
+        }
+        position.x.abs().max(position.z.abs()) - self.play_area_half_extent
+    }
+
+    // The play area is a horizontal square plus a ceiling, so altitude alone never counts
+    // as leaving it unless the plane climbs past the ceiling. The edge is soft: the plane
+    // is only out once it's more than `edge_slack` past it.
+    pub fn is_out_of_bounds(&self, position: Vec3, edge_slack: f32) -> bool {
+        self.distance_past_edge(position) > edge_slack || position.y > self.max_altitude
+    }
+
+    // Islands and clouds expected in an area of the given size, keeping the world's density
+    pub fn density_scaled(&self, count: usize, area: f32) -> f32 {
+        count as f32 * area / (self.water_visual_size * self.water_visual_size)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum WaterCollider {
+    // Thin sensor at the surface; the plane is held on top of the water
+    Surface,
+    // Sensor box extending `depth` below the surface; the plane can dive and buoyancy pushes it back up.
+    // Should be deeper than WATER_MAX_SAFE_DEPTH so a crash is detected before leaving the volume.
+    Volume { depth: f32 },
+}
+
+// Index into VIEW_PRESETS of the latched numpad view, or None for the chase camera
+#[derive(Resource, Default)]
+pub struct ViewPreset(pub Option<usize>);
+
+// How the follow camera tracks the plane when no numpad view is latched. Cycled with C.
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CameraMode {
+    #[default]
+    Chase,
+    Cockpit,
+    Orbit,
+}
+
+impl CameraMode {
+    pub fn next(self) -> Self {
+        match self {
+            CameraMode::Chase => CameraMode::Cockpit,
+            CameraMode::Cockpit => CameraMode::Orbit,
+            CameraMode::Orbit => CameraMode::Chase,
+        }
+    }
+}
+
+// Where the orbit camera sits around the plane, steered with the mouse.
+// Yaw is measured from straight behind the plane; pitch is the angle above the horizon.
+#[derive(Resource)]
+pub struct OrbitCamera {
+    pub yaw: f32,
+    pub pitch: f32,
+}
+
+impl Default for OrbitCamera {
+    fn default() -> Self {
+        Self { yaw: 0.0, pitch: 0.3 }
+    }
+}
+
+// Chase camera tuning. The mouse wheel zooms `distance` between the zoom limits;
+// the height and the sideways swing when banking scale with it to keep the same angle.
+#[derive(Resource)]
+pub struct CameraSettings {
+    pub distance: f32, // How far behind the plane the camera sits
+    pub height: f32, // How far above the plane it sits at CHASE_CAMERA_DISTANCE
+    pub min_zoom: f32,
+    pub max_zoom: f32,
+    pub position_response_time: f32, // Seconds to close ~63% of the gap to its spot; lower is snappier
+    pub rotation_response_time: f32, // Seconds to turn ~63% of the way to its view direction
+    pub bank_tilt: f32, // Share of the plane's bank the horizon tilts with, 0 (level) to 1
+    pub min_distance: f32, // Closest the camera is pulled in when an island blocks the view
+}
+
+impl Default for CameraSettings {
+    fn default() -> Self {
+        Self {
+            distance: CHASE_CAMERA_DISTANCE,
+            height: CHASE_CAMERA_HEIGHT,
+            min_zoom: CHASE_CAMERA_MIN_ZOOM,
+            max_zoom: CHASE_CAMERA_MAX_ZOOM,
+            position_response_time: CHASE_CAMERA_RESPONSE_TIME,
+            rotation_response_time: CHASE_CAMERA_ROTATION_RESPONSE_TIME,
+            bank_tilt: CHASE_CAMERA_BANK_TILT,
+            min_distance: CAMERA_MIN_DISTANCE,
+        }
+    }
+}
+
+impl CameraSettings {
+    // Distance relative to the default, which the height and bank swing are scaled by
+    pub fn zoom_scale(&self) -> f32 {
+        self.distance / CHASE_CAMERA_DISTANCE
+    }
+}
+
+// Clock for the day/night cycle, in hours (0..24). A full day takes `day_length` seconds.
+#[derive(Resource)]
+pub struct TimeOfDay {
+    pub hour: f32,
+    pub day_length: f32,
+}
+
+impl Default for TimeOfDay {
+    fn default() -> Self {
+        Self {
+            hour: START_HOUR,
+            day_length: DAY_LENGTH,
+        }
+    }
+}
+
+// What the player is doing this session. Chosen at launch with `--mode`.
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameMode {
+    #[default]
+    FreeFlight, // Just the world, no objectives or hazards
+    TimeTrial, // Race through a ring course against the clock
+    Combat, // Dogfight AI planes with weapons enabled
+    Landing, // Approach and land at a target island
+}
+
+impl GameMode {
+    pub fn from_arg(arg: &str) -> Option<Self> {
+        match arg {
+            "free" | "free-flight" => Some(Self::FreeFlight),
+            "time-trial" => Some(Self::TimeTrial),
+            "combat" => Some(Self::Combat),
+            "landing" => Some(Self::Landing),
+            _ => None,
+        }
+    }
+}
+
+// Rendering cost trade-offs, chosen at launch with `--graphics`. Applied once at
+// startup: TAA is a plugin and the clouds are only spawned when the world is built.
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphicsQuality {
+    Low, // Fewer clouds, small shadow maps, no cloud shadows, anti-aliasing, bloom or motion blur
+    Medium, // Half the clouds and shadow resolution, FXAA instead of TAA
+    #[default]
+    High, // Everything on
+}
+
+impl GraphicsQuality {
+    pub fn from_arg(arg: &str) -> Option<Self> {
+        match arg {
+            "low" => Some(Self::Low),
+            "medium" => Some(Self::Medium),
+            "high" => Some(Self::High),
+            _ => None,
+        }
+    }
+
+    // Clouds to spawn out of the world's full count
+    pub fn cloud_count(self, full: usize) -> usize {
+        match self {
+            Self::Low => full / 4,
+            Self::Medium => full / 2,
+            Self::High => full,
+        }
+    }
+
+    pub fn shadow_map_size(self) -> usize {
+        match self {
+            Self::Low => 1024,
+            Self::Medium => 2048,
+            Self::High => 4096,
+        }
+    }
+
+    pub fn anti_aliasing(self) -> AntiAliasing {
+        match self {
+            Self::Low => AntiAliasing::Off,
+            Self::Medium => AntiAliasing::Fxaa,
+            Self::High => AntiAliasing::Taa,
+        }
+    }
+
+    pub fn cloud_shadows(self) -> bool {
+        self != Self::Low
+    }
+
+    pub fn bloom(self) -> bool {
+        self != Self::Low
+    }
+
+    pub fn motion_blur(self) -> bool {
+        self != Self::Low
+    }
+}
+
+// Seeded random number generator for procedural placement of the world (islands,
+// clouds, birds, the ring course). The same seed builds the same world.
+// Chosen with `--seed` or AERO_ROSSO_SEED; otherwise random and logged so a run can be replayed.
+#[derive(Resource)]
+pub struct WorldSeed {
+    pub seed: u64,
+    pub rng: StdRng,
+}
+
+impl WorldSeed {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    // Generator for one chunk of the endless ocean, so a chunk always comes back the
+    // same however the plane got there
+    pub fn chunk_rng(&self, cell: IVec2) -> StdRng {
+        let cell_hash = (cell.x as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+            ^ (cell.y as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F);
+        StdRng::seed_from_u64(self.seed ^ cell_hash)
+    }
+}
+
+// Seeded random number generator for everything random while flying (turbulence, camera
+// shake, particles, cloud wrapping, bird strikes), so a seed plays out the same way every
+// time. It's a separate stream from WorldSeed's, so building a bigger or smaller world
+// doesn't shift the runtime draws. Systems that draw from it are chained, which keeps
+// the order of the draws fixed too. While a replay runs, draws come from a copy of the
+// recording's generator instead, and the live stream picks up where it left off afterwards.
+#[derive(Resource)]
+pub struct GameRng {
+    live: StdRng,
+    playback: Option<StdRng>,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            live: StdRng::seed_from_u64(seed ^ GAME_RNG_STREAM),
+            playback: None,
+        }
+    }
+
+    // The generator to draw from: the replay's while one runs, otherwise the live one
+    pub fn rng(&mut self) -> &mut StdRng {
+        self.playback.as_mut().unwrap_or(&mut self.live)
+    }
+
+    // The live generator's current state, for a replay to draw the same values again
+    pub fn snapshot(&self) -> StdRng {
+        self.live.clone()
+    }
+
+    // Draw from a copy of `snapshot` until the replay ends, leaving the live stream untouched
+    pub fn start_playback(&mut self, snapshot: &StdRng) {
+        self.playback = Some(snapshot.clone());
+    }
+
+    pub fn end_playback(&mut self) {
+        self.playback = None;
+    }
+}
+
+// Anti-aliasing on the flight camera. Follows the graphics quality unless chosen with `--aa`.
+// MSAA doesn't ghost on fast-moving things like the propeller and wake the way TAA can.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AntiAliasing {
+    Off,
+    Msaa, // 4x multisampling
+    Fxaa, // Cheap post-process edge smoothing
+    Taa, // Temporal; smoothest edges, may ghost on fast motion
+}
+
+impl AntiAliasing {
+    pub fn from_arg(arg: &str) -> Option<Self> {
+        match arg {
+            "off" => Some(Self::Off),
+            "msaa" => Some(Self::Msaa),
+            "fxaa" => Some(Self::Fxaa),
+            "taa" => Some(Self::Taa),
+            _ => None,
+        }
+    }
+}
+
+// Zero-G sandbox, toggled with F4. `normal` holds the gravity to go back to while
+// zero-G is on and until the gravity has eased back in after it.
+#[derive(Resource, Default)]
+pub struct ZeroG {
+    pub enabled: bool,
+    pub normal: Option<Vec3>,
+}
+
+// Fly with the mouse instead of (as well as) the keys and sticks. Toggled with M,
+// which also locks and hides the cursor while it's on.
+#[derive(Resource, Default)]
+pub struct MouseFlight {
+    pub enabled: bool,
+}
+
+// The roll, pitch and yaw keys as smoothed axes, each easing toward the held direction
+// and back to center on release, so a tap gives a small correction rather than full
+// deflection. Follows the keys exactly when `keyboard_smoothing` is off.
+#[derive(Resource, Default)]
+pub struct KeyboardAxes {
+    pub roll: f32,
+    pub pitch: f32,
+    pub yaw: f32,
+}
+
+// Random bumps close over the water and in clouds. Toggled with F9, or off from the
+// start with --no-turbulence.
+#[derive(Resource)]
+pub struct Turbulence {
+    pub enabled: bool,
+}
+
+impl Default for Turbulence {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+// Auto-throttle holds a target airspeed by adjusting power. Toggled with T;
+// while engaged the throttle keys dial the setpoint instead of the power.
+#[derive(Resource, Default)]
+pub struct AutoThrottle {
+    pub enabled: bool,
+    pub target_speed: f32,
+}
+
+// Values shown on the HUD, eased toward the true flight state so the numbers don't flicker
+#[derive(Resource, Default)]
+pub struct HudDisplay {
+    pub initialized: bool,
+    pub speed: f32,
+    pub altitude: f32,
+    pub momentum: Vec3,
+    pub impact_bounce: f32,
+    pub bank_angle: f32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn landing_slows_to_sailing_then_stops() {
+        let mut state = WaterState::Landing;
+        state = state.next(WATER_STOP_THRESHOLD + 10.0, 0.0, WATER_STOP_THRESHOLD);
+        assert_eq!(state, WaterState::Landing);
+        state = state.next(WATER_STOP_THRESHOLD - 1.0, 0.0, WATER_STOP_THRESHOLD);
+        assert_eq!(state, WaterState::Sailing);
+        state = state.next(WATER_STOPPED_SPEED + 0.1, 0.0, WATER_STOP_THRESHOLD);
+        assert_eq!(state, WaterState::Sailing);
+        state = state.next(WATER_STOPPED_SPEED - 0.1, 0.0, WATER_STOP_THRESHOLD);
+        assert_eq!(state, WaterState::Stopped);
+        assert_eq!(state.next(0.0, 0.0, WATER_STOP_THRESHOLD), WaterState::Stopped);
+    }
+
+    #[test]
+    fn power_starts_a_takeoff_from_any_state() {
+        for state in [WaterState::Landing, WaterState::Sailing, WaterState::Stopped, WaterState::TakingOff] {
+            assert_eq!(state.next(0.0, 0.5, WATER_STOP_THRESHOLD), WaterState::TakingOff);
+        }
+    }
+
+    #[test]
+    fn takeoff_run_holds_until_the_power_is_cut() {
+        let state = WaterState::TakingOff;
+        assert_eq!(state.next(30.0, 1.0, WATER_STOP_THRESHOLD), WaterState::TakingOff);
+        // Cutting the power at speed slows the plane like a landing, and coasts when slow
+        assert_eq!(state.next(30.0, 0.0, WATER_STOP_THRESHOLD), WaterState::Landing);
+        assert_eq!(state.next(1.0, 0.0, WATER_STOP_THRESHOLD), WaterState::Sailing);
+    }
+
+    // A play area 1000 across: the edge is 500 out on X and Z
+    fn square_world() -> WorldConfig {
+        let mut world_config = WorldConfig::with_size(1000.0);
+        world_config.play_area_half_extent = 500.0;
+        world_config
+    }
+
+    #[test]
+    fn high_but_centered_is_in_bounds() {
+        let world_config = square_world();
+        let below_ceiling = world_config.max_altitude - 1.0;
+        assert!(!world_config.is_out_of_bounds(Vec3::new(0.0, below_ceiling, 0.0), 0.0));
+        assert!(world_config.is_out_of_bounds(Vec3::new(0.0, world_config.max_altitude + 1.0, 0.0), 0.0));
+    }
+
+    #[test]
+    fn edge_is_measured_on_x_and_z_separately() {
+        let world_config = square_world();
+        assert_eq!(world_config.distance_past_edge(Vec3::ZERO), -500.0);
+        assert_eq!(world_config.distance_past_edge(Vec3::new(-520.0, 100.0, 30.0)), 20.0);
+        assert_eq!(world_config.distance_past_edge(Vec3::new(10.0, 0.0, 510.0)), 10.0);
+    }
+
+    #[test]
+    fn at_the_edge_is_still_in_bounds() {
+        let world_config = square_world();
+        assert!(!world_config.is_out_of_bounds(Vec3::new(500.0, 50.0, 0.0), 0.0));
+        assert!(world_config.is_out_of_bounds(Vec3::new(500.1, 50.0, 0.0), 0.0));
+    }
+
+    #[test]
+    fn edge_slack_lets_the_plane_past_the_edge() {
+        let world_config = square_world();
+        let just_out = Vec3::new(0.0, 50.0, -550.0);
+        assert!(!world_config.is_out_of_bounds(just_out, 100.0));
+        assert!(world_config.is_out_of_bounds(just_out, 40.0));
+    }
+
+    #[test]
+    fn corners_are_inside_the_square() {
+        // Outside a circle of the same half extent, but inside the square
+        let world_config = square_world();
+        assert!(!world_config.is_out_of_bounds(Vec3::new(490.0, 50.0, -490.0), 0.0));
+        assert!(world_config.is_out_of_bounds(Vec3::new(510.0, 50.0, -510.0), 0.0));
+    }
+
+    #[test]
+    fn endless_ocean_has_no_edge() {
+        let mut world_config = square_world();
+        world_config.endless = true;
+        assert!(!world_config.is_out_of_bounds(Vec3::new(1.0e6, 50.0, -1.0e6), 0.0));
+    }
+
+    fn draws(game_rng: &mut GameRng) -> Vec<u32> {
+        (0..8).map(|_| game_rng.rng().gen_range(0..u32::MAX)).collect()
+    }
+
+    #[test]
+    fn snapshot_replays_the_same_draws() {
+        let mut game_rng = GameRng::new(11);
+        let snapshot = game_rng.snapshot();
+        let first = draws(&mut game_rng);
+        game_rng.start_playback(&snapshot);
+        assert_eq!(draws(&mut game_rng), first);
+    }
+
+    #[test]
+    fn playback_leaves_the_live_stream_where_it_was() {
+        let mut game_rng = GameRng::new(11);
+        let mut untouched = GameRng::new(11);
+        let snapshot = game_rng.snapshot();
+        draws(&mut game_rng);
+        draws(&mut untouched);
+
+        game_rng.start_playback(&snapshot);
+        draws(&mut game_rng);
+        game_rng.end_playback();
+        assert_eq!(draws(&mut game_rng), draws(&mut untouched));
+    }
+
+    #[test]
+    fn exit_key_choices_are_unbound_keys() {
+        assert_eq!(QuitSettings::exit_key_from_arg("f10"), Some(KeyCode::F10));
+        assert_eq!(QuitSettings::exit_key_from_arg("escape"), Some(QuitSettings::default().exit_key));
+        assert_eq!(QuitSettings::exit_key_from_arg("q"), None);
+
+        // None of the choices would also fly the plane
+        let key_bindings = KeyBindings::default();
+        let flight_keys = [
+            key_bindings.pitch_up, key_bindings.pitch_down, key_bindings.roll_left, key_bindings.roll_right,
+            key_bindings.yaw_left, key_bindings.yaw_right, key_bindings.throttle_up, key_bindings.throttle_down,
+            key_bindings.trim_up, key_bindings.trim_down, key_bindings.boost,
+        ];
+        for arg in ["escape", "backspace", "f10", "f11", "pause", "end", "delete"] {
+            let key = QuitSettings::exit_key_from_arg(arg).unwrap();
+            assert!(!flight_keys.contains(&key), "{} is a flight key", arg);
+        }
+    }
 }
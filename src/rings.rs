@@ -0,0 +1,151 @@
+use bevy::prelude::*;
+use rand::Rng;
+use crate::components::{Plane, Ring};
+use crate::constants::*;
+
+// The ring course: every ring's center in flying order, and which one is up next.
+// Only the next ring exists as an entity, so the course leads the plane ring by ring.
+#[derive(Resource)]
+pub struct RingCourse {
+    pub centers: Vec<Vec3>,
+    pub next: usize,
+    mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+}
+
+impl RingCourse {
+    pub fn remaining(&self) -> usize {
+        self.centers.len() - self.next
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct Score(pub u32);
+
+// Lay out a winding course ahead of the spawn point and put up its first ring.
+// The course turns back toward the center whenever it strays past `extent`.
+pub fn spawn_rings(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    extent: f32,
+    rng: &mut impl Rng,
+) {
+    let mut centers = Vec::with_capacity(RING_COUNT);
+    let mut position = Vec3::new(0.0, 20.0, 0.0);
+    let mut heading: f32 = 0.0; // Radians from +Z, the direction the plane starts facing
+
+    for _ in 0..RING_COUNT {
+        if position.with_y(0.0).length() > extent {
+            heading = (-position.x).atan2(-position.z);
+        }
+        heading += rng.gen_range(-RING_MAX_TURN..RING_MAX_TURN);
+        position += Vec3::new(heading.sin(), 0.0, heading.cos()) * RING_SPACING;
+        position.y = rng.gen_range(RING_MIN_ALTITUDE..RING_MAX_ALTITUDE);
+        centers.push(position);
+    }
+
+    let mut course = RingCourse {
+        centers,
+        next: 0,
+        mesh: meshes.add(Torus::new(RING_RADIUS - RING_THICKNESS, RING_RADIUS)),
+        material: materials.add(StandardMaterial {
+            base_color: Color::srgb(1.0, 0.8, 0.1),
+            emissive: LinearRgba::rgb(1.5, 1.0, 0.0),
+            ..default()
+        }),
+    };
+    spawn_next_ring(commands, &mut course);
+    commands.insert_resource(course);
+}
+
+fn spawn_next_ring(commands: &mut Commands, course: &mut RingCourse) {
+    let Some(&center) = course.centers.get(course.next) else {
+        return;
+    };
+
+    // Face the ring along the leg of the course it sits on
+    let previous = course
+        .next
+        .checked_sub(1)
+        .map_or(Vec3::new(0.0, 20.0, 0.0), |index| course.centers[index]);
+    let direction = (center - previous).try_normalize().unwrap_or(Vec3::Z);
+
+    commands.spawn((
+        Mesh3d(course.mesh.clone()),
+        MeshMaterial3d(course.material.clone()),
+        // The torus lies flat around Y, so stand it up with Y along the course
+        Transform::from_translation(center).with_rotation(Quat::from_rotation_arc(Vec3::Y, direction)),
+        Ring {
+            radius: RING_RADIUS,
+            index: course.next,
+        },
+    ));
+}
+
+// Score a ring when the plane flies within its radius of the center, then put up the next one
+pub fn ring_detection(
+    mut commands: Commands,
+    course: Option<ResMut<RingCourse>>,
+    mut score: ResMut<Score>,
+    plane_query: Query<&Transform, With<Plane>>,
+    ring_query: Query<(Entity, &Transform, &Ring)>,
+) {
+    let Some(mut course) = course else {
+        return;
+    };
+    let Ok(plane_transform) = plane_query.get_single() else {
+        return;
+    };
+
+    for (entity, ring_transform, ring) in ring_query.iter() {
+        if ring.index != course.next
+            || plane_transform.translation.distance(ring_transform.translation) > ring.radius
+        {
+            continue;
+        }
+
+        score.0 += 1;
+        course.next += 1;
+        commands.entity(entity).despawn();
+        if course.remaining() == 0 {
+            info!("Ring course complete! Score: {}", score.0);
+        } else {
+            spawn_next_ring(&mut commands, &mut course);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+    use crate::resources::WorldSeed;
+
+    // Ring centers of the time-trial course laid out from `seed`
+    fn course_from_seed(seed: u64) -> Vec<Vec3> {
+        let mut world = World::new();
+        world.insert_resource(WorldSeed::new(seed));
+        world.init_resource::<Assets<Mesh>>();
+        world.init_resource::<Assets<StandardMaterial>>();
+        world
+            .run_system_once(
+                |mut commands: Commands,
+                 mut meshes: ResMut<Assets<Mesh>>,
+                 mut materials: ResMut<Assets<StandardMaterial>>,
+                 mut world_seed: ResMut<WorldSeed>| {
+                    spawn_rings(&mut commands, &mut meshes, &mut materials, 500.0, &mut world_seed.rng);
+                },
+            )
+            .unwrap();
+        world.resource::<RingCourse>().centers.clone()
+    }
+
+    #[test]
+    fn same_seed_lays_out_the_same_course() {
+        let course = course_from_seed(42);
+        assert_eq!(course.len(), RING_COUNT);
+        assert_eq!(course_from_seed(42), course);
+        assert_ne!(course_from_seed(43), course);
+    }
+}
@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+
+// Frame phases, configured to run in this order. Input runs in RunFixedMainLoop just
+// before the fixed steps, so the physics sees this frame's controls. Physics steps in
+// FixedUpdate at PHYSICS_TIMESTEP_HZ so handling doesn't depend on the frame rate; the
+// rest run in Update.
+// Systems that touch the same data within a phase are chained where they're registered.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GameSet {
+    Input, // Read devices and update control state (RunFixedMainLoop, before FixedUpdate)
+    Physics, // Integrate the plane's flight and water physics (FixedUpdate)
+    PostPhysics, // World updates that depend on the plane's new state (clouds, effects)
+    Camera, // Position cameras against this frame's plane transform
+    Ui, // Refresh the HUD from the final state of the frame
+}
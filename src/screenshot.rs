@@ -0,0 +1,55 @@
+use bevy::{
+    prelude::*,
+    render::view::screenshot::{Screenshot, ScreenshotCaptured, save_to_disk},
+};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::constants::SCREENSHOT_DIR;
+use crate::resources::ScreenshotSettings;
+
+// F12 saves the current frame as a PNG named after the time it was taken.
+// Without the HUD, every top-level UI node is hidden for the captured frame
+// and put back as it was once the image has been read back.
+#[allow(clippy::type_complexity)]
+pub fn take_screenshot(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    screenshot_settings: Res<ScreenshotSettings>,
+    mut ui_query: Query<(Entity, &mut Visibility), (With<Node>, Without<Parent>)>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::F12) {
+        return;
+    }
+
+    if let Err(error) = std::fs::create_dir_all(SCREENSHOT_DIR) {
+        error!("Cannot create the {} folder: {}", SCREENSHOT_DIR, error);
+        return;
+    }
+
+    // Milliseconds keep shots taken in quick succession from overwriting each other
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    let path = Path::new(SCREENSHOT_DIR).join(format!(
+        "aero-rosso-{}-{:03}.png",
+        timestamp.as_secs(),
+        timestamp.subsec_millis(),
+    ));
+
+    let mut screenshot = commands.spawn(Screenshot::primary_window());
+    screenshot.observe(save_to_disk(path));
+
+    if screenshot_settings.include_hud {
+        return;
+    }
+
+    let hidden: Vec<(Entity, Visibility)> = ui_query
+        .iter_mut()
+        .map(|(entity, mut visibility)| (entity, std::mem::replace(&mut *visibility, Visibility::Hidden)))
+        .collect();
+    screenshot.observe(move |_: Trigger<ScreenshotCaptured>, mut visibility_query: Query<&mut Visibility>| {
+        for (entity, previous) in &hidden {
+            if let Ok(mut visibility) = visibility_query.get_mut(*entity) {
+                *visibility = *previous;
+            }
+        }
+    });
+}
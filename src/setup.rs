@@ -2,26 +2,48 @@ use bevy::{
     prelude::*,
     render::{
         camera::Projection,
+        mesh::{Indices, PrimitiveTopology},
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
     },
-    core_pipeline::bloom::Bloom,
+    core_pipeline::{bloom::Bloom, experimental::taa::TemporalAntiAliasing, fxaa::Fxaa},
+    pbr::NotShadowCaster,
 };
 use avian3d::prelude::*;
-use rand::{thread_rng, Rng};
-use std::f32::consts::PI;
+use rand::Rng;
+use std::f32::consts::{PI, TAU};
+use std::ops::Range;
 
-use crate::components::{Plane, FollowCamera, Island, Cloud, Water};
+use crate::components::{Plane, Propeller, Sun, FillLight, FollowCamera, Island, IslandKind, IslandLod, Cloud, CloudLayer, CloudPuff, CloudShadow, Water, Thermal};
+use crate::environment_systems::cloud_shadow_alpha;
 use crate::constants::*;
+use crate::resources::{WorldConfig, WaterCollider, GraphicsQuality, AntiAliasing, WorldSeed, KeyBindings, QuitSettings};
 use crate::ui::setup_ui;
+use crate::waves::WaterWaves;
+use crate::plane_systems::plane_spawn_transform;
+use crate::minimap::setup_minimap;
+use crate::birds::spawn_birds;
 use crate::atmospheric;
+use crate::day_night::spawn_sky_dome;
 
+#[allow(clippy::too_many_arguments)]
 pub fn setup(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
-    _images: ResMut<Assets<Image>>,
+    mut images: ResMut<Assets<Image>>,
     asset_server: Res<AssetServer>,
+    world_config: Res<WorldConfig>,
+    graphics_quality: Res<GraphicsQuality>,
+    anti_aliasing: Res<AntiAliasing>,
+    mut world_seed: ResMut<WorldSeed>,
+    key_bindings: Res<KeyBindings>,
+    quit_settings: Res<QuitSettings>,
 ) {
+    info!("World seed: {} (pass --seed {} to rebuild this world)", world_seed.seed, world_seed.seed);
+
     // Create water with physics collider
+    let water_size = world_config.water_visual_size;
     let water_material = materials.add(StandardMaterial {
         base_color: Color::srgba(0.0, 0.5, 0.8, 0.9),
         perceptual_roughness: 0.1,
@@ -30,80 +52,49 @@ pub fn setup(
         ..default()
     });
 
-    let _water_entity = commands.spawn((
-        Mesh3d(meshes.add(Plane3d::new(Vec3::Y, Vec2::new(WATER_SIZE, WATER_SIZE)).mesh().size(WATER_SIZE, WATER_SIZE))),
+    // Waves need vertices to move, so subdivide the surface when they're on
+    let water_subdivisions = if world_config.water_waves { WATER_SUBDIVISIONS } else { 0 };
+    let water_mesh = Plane3d::new(Vec3::Y, Vec2::new(water_size, water_size))
+        .mesh()
+        .size(water_size, water_size)
+        .subdivisions(water_subdivisions);
+
+    let water_entity = commands.spawn((
+        Mesh3d(meshes.add(water_mesh)),
         MeshMaterial3d(water_material),
         Transform::from_xyz(0.0, 0.0, 0.0),
         Water,
         RigidBody::Static,
-        Collider::cuboid(WATER_SIZE/2.0, 0.1, WATER_SIZE/2.0),
-        Sensor::default(), // Make it a sensor to detect collisions without physical response
+        water_collider(water_size, world_config.water_collider, world_config.water_waves),
+        Sensor, // Make it a sensor to detect collisions without physical response
         Friction::new(0.8), // High friction to slow down plane on water
     )).id();
 
-    // Create islands
-    let island_mesh = meshes.add(Mesh::from(Cylinder {
-        radius: 10.0,
-        half_height: 2.5,
-    }));
-
-    let island_material = materials.add(StandardMaterial {
-        base_color: Color::srgb(0.1, 0.8, 0.2),
-        perceptual_roughness: 0.9,
-        ..default()
-    });
-
-    let mut rng = thread_rng();
-    for _ in 0..ISLAND_COUNT {
-        let x = rng.gen_range(-WATER_SIZE/2.5..WATER_SIZE/2.5);
-        let z = rng.gen_range(-WATER_SIZE/2.5..WATER_SIZE/2.5);
-        let scale = rng.gen_range(0.5..2.0);
-        
-        commands.spawn((
-            Mesh3d(island_mesh.clone()),
-            MeshMaterial3d(island_material.clone()),
-            Transform::from_xyz(x, 0.0, z)
-                .with_scale(Vec3::new(scale, scale * 0.5, scale)),
-            Island,
-            RigidBody::Static,
-            Collider::cylinder(2.5, 10.0),
-        ));
+    if world_config.water_waves {
+        commands.entity(water_entity).insert(WaterWaves);
     }
 
-    // Create clouds
-    let cloud_mesh = meshes.add(Mesh::from(Cuboid::new(1.0, 1.0, 1.0)));
-    let cloud_material = materials.add(StandardMaterial {
-        base_color: Color::srgba(1.0, 1.0, 1.0, 0.8),
-        perceptual_roughness: 1.0,
-        alpha_mode: AlphaMode::Blend,
-        ..default()
-    });
+    // Create islands, one mesh and collider per kind shared by every island of it.
+    // The terrain colors are in the mesh, so the material only sets the finish.
+    let world_assets = WorldAssets::new(&mut meshes, &mut materials, &mut images, world_config.billboard_clouds);
 
-    for _ in 0..CLOUD_COUNT {
-        let x = rng.gen_range(-WATER_SIZE/2.0..WATER_SIZE/2.0);
-        let y = rng.gen_range(30.0..80.0);
-        let z = rng.gen_range(-WATER_SIZE/2.0..WATER_SIZE/2.0);
-        let scale_x = rng.gen_range(5.0..15.0);
-        let scale_y = rng.gen_range(2.0..5.0);
-        let scale_z = rng.gen_range(5.0..15.0);
-        let cloud_speed = rng.gen_range(0.5..2.0);
-        
-        commands.spawn((
-            Mesh3d(cloud_mesh.clone()),
-            MeshMaterial3d(cloud_material.clone()),
-            Transform::from_xyz(x, y, z)
-                .with_scale(Vec3::new(scale_x, scale_y, scale_z)),
-            Cloud {
-                speed: cloud_speed,
-            },
-        ));
+    // In the endless ocean, islands and clouds are streamed in around the plane instead
+    let rng = &mut world_seed.rng;
+    if !world_config.endless {
+        // Keep islands away from the edge of the spawn area
+        let island_range = world_config.spawn_radius * 0.8;
+        let cloud_range = world_config.spawn_radius;
+
+        for _ in 0..world_config.island_count {
+            spawn_island(&mut commands, &world_assets, -island_range..island_range, -island_range..island_range, &mut *rng);
+        }
+        for _ in 0..graphics_quality.cloud_count(world_config.cloud_count) {
+            spawn_cloud(&mut commands, &mut materials, &world_assets, *graphics_quality, -cloud_range..cloud_range, -cloud_range..cloud_range, &mut *rng);
+        }
     }
+    commands.insert_resource(world_assets);
 
     // Create the plane
-    let plane_body = meshes.add(Mesh::from(Cuboid::new(2.0, 0.5, 4.0)));
-    let plane_wing = meshes.add(Mesh::from(Cuboid::new(8.0, 0.2, 1.5)));
-    let plane_tail = meshes.add(Mesh::from(Cuboid::new(2.0, 1.0, 0.2)));
-    
     let red_material = materials.add(StandardMaterial {
         base_color: Color::srgb(0.9, 0.1, 0.1),
         perceptual_roughness: 0.2,
@@ -111,29 +102,12 @@ pub fn setup(
         emissive: Color::srgb(0.8, 0.2, 0.2).into(),
         ..default()
     });
-    
-    let white_material = materials.add(StandardMaterial {
-        base_color: Color::srgb(0.9, 0.9, 0.9),
-        perceptual_roughness: 0.2,
-        metallic: 0.8,
-        emissive: Color::srgb(0.5, 0.5, 0.5).into(),
-        ..default()
-    });
 
-    // Create a parent entity for the plane
-    let plane_entity = commands.spawn_empty().id();
-    
-    // Add components to the plane entity
+    let plane_entity = spawn_plane(&mut commands, &mut meshes, &mut materials, plane_spawn_transform(), red_material);
+
     commands.entity(plane_entity).insert((
-        Mesh3d(meshes.add(Mesh::from(Cuboid::new(1.0, 0.25, 2.0)))),
-        MeshMaterial3d(red_material.clone()),
-        Transform::from_xyz(0.0, 20.0, 0.0)
-            .with_rotation(Quat::from_rotation_y(PI))
-            .with_scale(Vec3::splat(PLANE_SCALE)),
         Plane,
         Name::new("Plane"),
-        Visibility::Visible,
-        InheritedVisibility::default(),
     ));
     
     // Add physics components
@@ -145,18 +119,165 @@ pub fn setup(
         CollidingEntities::default(), // Track collisions
         LinearVelocity::default(),
         AngularVelocity::default(),
-        GravityScale(1.0),
+        GravityScale(0.0), // plane_physics integrates gravity into the momentum itself
         Restitution::new(0.3), // Bounciness
         Friction::new(0.5), // Surface friction
-        TransformInterpolation::default(), // Smooth physics movement
+        TransformInterpolation, // Smooth physics movement
+    ));
+
+    // Add directional lights
+    commands.spawn((
+        DirectionalLight {
+            illuminance: 50000.0,
+            shadows_enabled: true,
+            ..default()
+        },
+        Transform::from_xyz(10.0, 50.0, 10.0)
+            .looking_at(Vec3::ZERO, Vec3::Y),
+        Sun,
     ));
     
+    commands.spawn((
+        DirectionalLight {
+            illuminance: 15000.0,
+            shadows_enabled: false,
+            ..default()
+        },
+        Transform::from_xyz(-10.0, 30.0, -10.0)
+            .looking_at(Vec3::ZERO, Vec3::Y),
+        FillLight,
+    ));
+
+    // Add 3D camera
+    let camera_entity = commands.spawn((
+        Camera3d::default(),
+        Camera {
+            clear_color: ClearColorConfig::Custom(Color::srgb(0.5, 0.8, 1.0)),
+            order: 1,
+            ..default()
+        },
+        Projection::Perspective(PerspectiveProjection {
+            fov: std::f32::consts::PI / 3.0,
+            near: 0.1,
+            far: 2000.0,
+            ..default()
+        }),
+        Transform::from_xyz(0.0, 30.0, 50.0)
+            .looking_at(Vec3::new(0.0, 20.0, 0.0), Vec3::Y),
+        FollowCamera {},
+        Name::new("Camera"),
+    )).id();
+    
+    if graphics_quality.bloom() {
+        commands.entity(camera_entity).insert(Bloom {
+            intensity: BLOOM_INTENSITY,
+            ..default()
+        });
+    }
+    // Only on the world camera; the UI camera draws the HUD crisp on top
+    if graphics_quality.motion_blur() {
+        atmospheric::add_motion_blur(&mut commands, camera_entity);
+    }
+    add_anti_aliasing(&mut commands, camera_entity, *anti_aliasing);
+    if world_config.gradient_sky {
+        spawn_sky_dome(&mut commands, &mut meshes, &mut materials);
+    }
+    
+    // Add 2D camera for UI overlay with a different priority to avoid ambiguity
+    commands.spawn((
+        Camera2d,
+        Camera {
+            order: 0, // Higher priority than the default 0 of the 3D camera
+            ..default()
+        },
+    ));
+    
+    // Setup UI for flight data display
+    setup_ui(&mut commands, &asset_server, &key_bindings, &quit_settings);
+    // The minimap charts the fixed world, which the endless ocean doesn't have
+    if !world_config.endless {
+        setup_minimap(&mut commands, &world_config);
+    }
+
+    spawn_birds(&mut commands, &mut meshes, &mut materials, &world_config, rng);
+}
+
+// Set up the chosen anti-aliasing on the flight camera. Cameras multisample by default,
+// so every mode but MSAA turns that off; TAA and FXAA need it off to work at all.
+fn add_anti_aliasing(commands: &mut Commands, camera_entity: Entity, anti_aliasing: AntiAliasing) {
+    let mut camera = commands.entity(camera_entity);
+    match anti_aliasing {
+        AntiAliasing::Off => {
+            camera.insert(Msaa::Off);
+        }
+        AntiAliasing::Msaa => {
+            camera.insert(Msaa::Sample4);
+        }
+        AntiAliasing::Fxaa => {
+            camera.insert((Msaa::Off, Fxaa::default()));
+        }
+        AntiAliasing::Taa => {
+            camera.insert((Msaa::Off, TemporalAntiAliasing::default()));
+        }
+    }
+}
+
+// Build the water sensor. Both shapes share the same top face at the surface,
+// raised to the wave crests when waves are on so the plane stays in contact riding them.
+fn water_collider(size: f32, shape: WaterCollider, waves: bool) -> Collider {
+    let top = WATER_SURFACE_THICKNESS / 2.0 + if waves { WAVE_AMPLITUDE } else { 0.0 };
+    match shape {
+        WaterCollider::Surface => {
+            let thickness = top * 2.0;
+            Collider::cuboid(size, thickness, size)
+        }
+        WaterCollider::Volume { depth } => {
+            Collider::compound(vec![(
+                Vec3::new(0.0, top - depth / 2.0, 0.0),
+                Quat::IDENTITY,
+                Collider::cuboid(size, depth, size),
+            )])
+        }
+    }
+}
+
+// Build a plane's model: a root entity with the body, wings, tail and propeller as
+// children, painted with `body_material` and white trim. Gameplay and physics components
+// are left to the caller, so the player and AI planes share the same model.
+pub fn spawn_plane(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    transform: Transform,
+    body_material: Handle<StandardMaterial>,
+) -> Entity {
+    let plane_body = meshes.add(Mesh::from(Cuboid::new(2.0, 0.5, 4.0)));
+    let plane_wing = meshes.add(Mesh::from(Cuboid::new(8.0, 0.2, 1.5)));
+    let plane_tail = meshes.add(Mesh::from(Cuboid::new(2.0, 1.0, 0.2)));
+
+    let white_material = materials.add(StandardMaterial {
+        base_color: Color::srgb(0.9, 0.9, 0.9),
+        perceptual_roughness: 0.2,
+        metallic: 0.8,
+        emissive: Color::srgb(0.5, 0.5, 0.5).into(),
+        ..default()
+    });
+
+    // Create a parent entity for the plane
+    let plane_entity = commands.spawn((
+        Mesh3d(meshes.add(Mesh::from(Cuboid::new(1.0, 0.25, 2.0)))),
+        MeshMaterial3d(body_material.clone()),
+        transform,
+        Visibility::Visible,
+        InheritedVisibility::default(),
+    )).id();
+    
     // Add child parts to the plane
     commands.entity(plane_entity).with_children(|parent| {
         // Plane body
         parent.spawn((
             Mesh3d(plane_body),
-            MeshMaterial3d(red_material.clone()),
+            MeshMaterial3d(body_material.clone()),
             Transform::default(),
             Visibility::Visible,
             InheritedVisibility::default(),
@@ -165,7 +286,7 @@ pub fn setup(
         // Plane wings
         parent.spawn((
             Mesh3d(plane_wing.clone()),
-            MeshMaterial3d(red_material.clone()),
+            MeshMaterial3d(body_material.clone()),
             Transform::from_xyz(0.0, 0.0, 0.0),
             Visibility::Visible,
             InheritedVisibility::default(),
@@ -187,7 +308,7 @@ pub fn setup(
         // Plane tail
         parent.spawn((
             Mesh3d(plane_tail),
-            MeshMaterial3d(red_material.clone()),
+            MeshMaterial3d(body_material.clone()),
             Transform::from_xyz(0.0, 0.5, -2.0),
         ));
         
@@ -206,65 +327,412 @@ pub fn setup(
                 ..default()
             })),
             Transform::from_xyz(0.0, 0.0, 2.1),
+            Propeller,
         ));
     });
 
-    // Add directional lights
-    commands.spawn((
-        DirectionalLight {
-            illuminance: 50000.0,
-            shadows_enabled: true,
-            ..default()
-        },
-        Transform::from_xyz(10.0, 50.0, 10.0)
-            .looking_at(Vec3::ZERO, Vec3::Y),
-    ));
-    
-    commands.spawn((
-        DirectionalLight {
-            illuminance: 15000.0,
-            shadows_enabled: false,
-            ..default()
-        },
-        Transform::from_xyz(-10.0, 30.0, -10.0)
-            .looking_at(Vec3::ZERO, Vec3::Y),
-    ));
+    plane_entity
+}
 
-    // Add 3D camera
-    let camera_entity = commands.spawn((
-        Camera3d::default(),
-        Camera {
-            clear_color: ClearColorConfig::Custom(Color::srgb(0.5, 0.8, 1.0)),
-            order: 1,
-            ..default()
+// Meshes, colliders and materials shared by every island and cloud. Kept as a resource
+// so the endless ocean can populate new chunks with them.
+#[derive(Resource)]
+pub struct WorldAssets {
+    islands: Vec<(IslandKind, Handle<Mesh>, Handle<Mesh>, Collider)>,
+    island_material: Handle<StandardMaterial>,
+    props: IslandPropAssets,
+    billboard_clouds: bool, // Clouds are clusters of puffs using cloud_mesh, rather than one cloud_mesh box
+    cloud_mesh: Handle<Mesh>,
+    cloud_material: Handle<StandardMaterial>,
+    high_cloud_material: Handle<StandardMaterial>,
+    cloud_shadow_mesh: Handle<Mesh>,
+}
+
+impl WorldAssets {
+    pub(crate) fn new(
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+        images: &mut Assets<Image>,
+        billboard_clouds: bool,
+    ) -> Self {
+        let islands = IslandKind::ALL
+            .iter()
+            .map(|&kind| {
+                let (mesh, collider) = island_shape(kind, ISLAND_GRID_RESOLUTION);
+                let (simple_mesh, _) = island_shape(kind, ISLAND_LOW_DETAIL_RESOLUTION);
+                (kind, meshes.add(mesh), meshes.add(simple_mesh), collider)
+            })
+            .collect();
+
+        // Billboard clouds draw a soft round texture on flat quads
+        let (cloud_mesh, cloud_texture) = if billboard_clouds {
+            (meshes.add(Rectangle::new(1.0, 1.0)), Some(images.add(cloud_puff_texture())))
+        } else {
+            (meshes.add(Cuboid::new(1.0, 1.0, 1.0)), None)
+        };
+
+        Self {
+            islands,
+            island_material: materials.add(StandardMaterial {
+                perceptual_roughness: 0.9,
+                ..default()
+            }),
+            props: IslandPropAssets {
+                trunk_mesh: meshes.add(Cylinder { radius: 0.2, half_height: 2.0 }),
+                crown_mesh: meshes.add(Cone { radius: 1.8, height: 1.0 }),
+                rock_mesh: meshes.add(Cuboid::new(1.0, 1.0, 1.0)),
+                trunk_material: materials.add(StandardMaterial {
+                    base_color: Color::srgb(0.45, 0.32, 0.2),
+                    perceptual_roughness: 1.0,
+                    ..default()
+                }),
+                crown_material: materials.add(StandardMaterial {
+                    base_color: Color::srgb(0.15, 0.55, 0.15),
+                    perceptual_roughness: 0.8,
+                    ..default()
+                }),
+                rock_material: materials.add(StandardMaterial {
+                    base_color: Color::srgb(0.5, 0.48, 0.45),
+                    perceptual_roughness: 1.0,
+                    ..default()
+                }),
+            },
+            billboard_clouds,
+            cloud_mesh,
+            cloud_material: materials.add(StandardMaterial {
+                base_color: Color::srgba(1.0, 1.0, 1.0, 0.8),
+                base_color_texture: cloud_texture.clone(),
+                perceptual_roughness: 1.0,
+                alpha_mode: AlphaMode::Blend,
+                ..default()
+            }),
+            high_cloud_material: materials.add(StandardMaterial {
+                base_color: Color::srgba(0.95, 0.97, 1.0, 0.45),
+                base_color_texture: cloud_texture,
+                perceptual_roughness: 1.0,
+                alpha_mode: AlphaMode::Blend,
+                ..default()
+            }),
+            cloud_shadow_mesh: meshes.add(Circle::new(0.5)),
+        }
+    }
+}
+
+// Place an island of a random kind and size somewhere in the given ranges, with its props
+// and the thermal rising over it. Returns every entity spawned.
+pub fn spawn_island(
+    commands: &mut Commands,
+    assets: &WorldAssets,
+    x_range: Range<f32>,
+    z_range: Range<f32>,
+    rng: &mut impl Rng,
+) -> [Entity; 2] {
+    let x = rng.gen_range(x_range);
+    let z = rng.gen_range(z_range);
+    let scale = rng.gen_range(0.5..2.0);
+    let (kind, mesh, simple_mesh, collider) = &assets.islands[rng.gen_range(0..assets.islands.len())];
+
+    let island = commands.spawn((
+        Mesh3d(mesh.clone()),
+        MeshMaterial3d(assets.island_material.clone()),
+        Transform::from_xyz(x, 0.0, z)
+            .with_scale(Vec3::new(scale, scale * 0.5, scale)),
+        Island { kind: *kind },
+        IslandLod {
+            detailed: mesh.clone(),
+            simple: simple_mesh.clone(),
         },
-        Projection::Perspective(PerspectiveProjection {
-            fov: std::f32::consts::PI / 3.0,
-            near: 0.1,
-            far: 2000.0,
-            ..default()
-        }),
-        Transform::from_xyz(0.0, 30.0, 50.0)
-            .looking_at(Vec3::new(0.0, 20.0, 0.0), Vec3::Y),
-        Bloom {
-            intensity: 0.3,
-            ..default()
+        RigidBody::Static,
+        collider.clone(),
+    )).with_children(|parent| {
+        spawn_island_props(parent, *kind, &assets.props, rng);
+    }).id();
+
+    // Warm air rises over each island, more strongly over the bigger ones
+    let thermal = commands.spawn(Thermal {
+        position: Vec3::new(x, 0.0, z),
+        radius: THERMAL_RADIUS_PER_SCALE * scale,
+        strength: THERMAL_STRENGTH_PER_SCALE * scale,
+    }).id();
+
+    [island, thermal]
+}
+
+// Place a cloud of random size and pace somewhere in the given ranges, with its shadow
+// on the water when those are on. Returns every entity spawned.
+pub fn spawn_cloud(
+    commands: &mut Commands,
+    materials: &mut Assets<StandardMaterial>,
+    assets: &WorldAssets,
+    graphics_quality: GraphicsQuality,
+    x_range: Range<f32>,
+    z_range: Range<f32>,
+    rng: &mut impl Rng,
+) -> Vec<Entity> {
+    let layer = if rng.gen_bool(HIGH_CLOUD_SHARE) { CloudLayer::High } else { CloudLayer::Low };
+    let x = rng.gen_range(x_range);
+    let z = rng.gen_range(z_range);
+    let cloud_speed = rng.gen_range(0.5..2.0);
+
+    // Low puffs sit just above the islands; the high sheets are wide and flat, and fainter
+    let (y, cloud_size, material) = match layer {
+        CloudLayer::Low => (
+            rng.gen_range(30.0..80.0),
+            Vec3::new(rng.gen_range(5.0..15.0), rng.gen_range(2.0..5.0), rng.gen_range(5.0..15.0)),
+            &assets.cloud_material,
+        ),
+        CloudLayer::High => (
+            rng.gen_range(180.0..260.0),
+            Vec3::new(rng.gen_range(25.0..60.0), rng.gen_range(0.5..1.5), rng.gen_range(25.0..60.0)),
+            &assets.high_cloud_material,
+        ),
+    };
+
+    let cloud = (
+        Transform::from_xyz(x, y, z)
+            .with_scale(cloud_size),
+        Cloud {
+            speed: cloud_speed,
+            size: cloud_size,
+            layer,
         },
-        FollowCamera {},
-        Name::new("Camera"),
-    )).id();
-    
-    atmospheric::add_motion_blur(&mut commands, camera_entity);
-    
-    // Add 2D camera for UI overlay with a different priority to avoid ambiguity
-    commands.spawn((
-        Camera2d::default(),
-        Camera {
-            order: 0, // Higher priority than the default 0 of the 3D camera
+    );
+    let mut entities = Vec::new();
+    if assets.billboard_clouds {
+        // An invisible box the puffs cluster in, spread out on a sunflower spiral with
+        // the bigger ones in the middle and a little up and down for a lumpy top
+        let cloud_entity = commands.spawn((cloud, Visibility::default())).id();
+        entities.push(cloud_entity);
+        let puff_count = match layer {
+            CloudLayer::Low => CLOUD_PUFFS_LOW,
+            CloudLayer::High => CLOUD_PUFFS_HIGH,
+        };
+        for index in 0..puff_count {
+            let spread = ((index as f32 + 0.5) / puff_count as f32).sqrt();
+            let angle = index as f32 * GOLDEN_ANGLE;
+            let offset = Vec3::new(
+                angle.cos() * spread * 0.35,
+                (index % 3) as f32 * 0.15 - 0.15,
+                angle.sin() * spread * 0.35,
+            );
+            entities.push(commands.spawn((
+                Mesh3d(assets.cloud_mesh.clone()),
+                MeshMaterial3d(material.clone()),
+                Transform::from_translation(Vec3::new(x, y, z) + offset * cloud_size),
+                CloudPuff {
+                    cloud: cloud_entity,
+                    offset,
+                    size: 0.6 - 0.25 * spread,
+                },
+                NotShadowCaster,
+            )).id());
+        }
+    } else {
+        entities.push(commands.spawn((Mesh3d(assets.cloud_mesh.clone()), MeshMaterial3d(material.clone()), cloud)).id());
+    }
+    let cloud_entity = entities[0];
+
+    // Clouds too high to darken the water get no shadow, and low quality skips them all
+    if graphics_quality.cloud_shadows() && cloud_shadow_alpha(y) > 0.0 {
+        // Each shadow gets its own material since the opacity depends on the cloud's altitude
+        let shadow_material = materials.add(StandardMaterial {
+            base_color: Color::srgba(0.0, 0.05, 0.1, cloud_shadow_alpha(y)),
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
             ..default()
-        },
-    ));
-    
-    // Setup UI for flight data display
-    setup_ui(&mut commands, &asset_server);
+        });
+
+        entities.push(commands.spawn((
+            Mesh3d(assets.cloud_shadow_mesh.clone()),
+            MeshMaterial3d(shadow_material),
+            Transform::from_xyz(x, CLOUD_SHADOW_HEIGHT, z)
+                .with_rotation(Quat::from_rotation_x(-PI / 2.0)),
+            CloudShadow {
+                cloud: cloud_entity,
+            },
+            NotShadowCaster,
+        )).id());
+    }
+
+    entities
+}
+
+// White texture with a round alpha falloff, soft all the way to the edge
+fn cloud_puff_texture() -> Image {
+    let size = CLOUD_PUFF_TEXTURE_SIZE;
+    let data = (0..size * size)
+        .flat_map(|index| {
+            let pixel = Vec2::new((index % size) as f32, (index / size) as f32) + 0.5;
+            let from_center = pixel / size as f32 * 2.0 - 1.0;
+            let alpha = (1.0 - from_center.length_squared()).max(0.0).powi(2);
+            [255, 255, 255, (alpha * 255.0) as u8]
+        })
+        .collect();
+    Image::new(
+        Extent3d { width: size, height: size, depth_or_array_layers: 1 },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    )
+}
+
+// Shared meshes and materials for the decorations scattered over the islands
+struct IslandPropAssets {
+    trunk_mesh: Handle<Mesh>,
+    crown_mesh: Handle<Mesh>,
+    rock_mesh: Handle<Mesh>,
+    trunk_material: Handle<StandardMaterial>,
+    crown_material: Handle<StandardMaterial>,
+    rock_material: Handle<StandardMaterial>,
+}
+
+// Scatter a few palm trees and rocks over an island as children of it. They're only
+// decoration, so they have no colliders. Children inherit the island's flattened scale,
+// so each prop stretches itself back upright.
+fn spawn_island_props(parent: &mut ChildBuilder, kind: IslandKind, assets: &IslandPropAssets, rng: &mut impl Rng) {
+    let unsquash = Vec3::new(1.0, 2.0, 1.0);
+    let radius = kind.footprint_radius();
+    let ground = |distance: f32, angle: f32| {
+        Vec3::new(distance * angle.cos(), island_height(kind, distance), distance * angle.sin())
+    };
+
+    // Nothing grows on the bare rock
+    let palm_count = if kind == IslandKind::Rocky { 0 } else { rng.gen_range(1..=ISLAND_MAX_PALMS) };
+    for _ in 0..palm_count {
+        let position = ground(rng.gen_range(0.0..radius * ISLAND_BEACH_START), rng.gen_range(0.0..TAU));
+        let lean = Quat::from_rotation_y(rng.gen_range(0.0..TAU)) * Quat::from_rotation_x(rng.gen_range(0.0..0.3));
+        let height = rng.gen_range(0.8..1.3);
+
+        parent.spawn((
+            Transform::from_translation(position)
+                .with_rotation(lean)
+                .with_scale(unsquash * height),
+            Visibility::default(),
+        )).with_children(|palm| {
+            palm.spawn((
+                Mesh3d(assets.trunk_mesh.clone()),
+                MeshMaterial3d(assets.trunk_material.clone()),
+                Transform::from_xyz(0.0, 2.0, 0.0),
+            ));
+            palm.spawn((
+                Mesh3d(assets.crown_mesh.clone()),
+                MeshMaterial3d(assets.crown_material.clone()),
+                Transform::from_xyz(0.0, 4.2, 0.0),
+            ));
+        });
+    }
+
+    for _ in 0..rng.gen_range(0..=ISLAND_MAX_ROCKS) {
+        let position = ground(rng.gen_range(0.0..radius), rng.gen_range(0.0..TAU));
+        let size = Vec3::new(rng.gen_range(0.6..1.8), rng.gen_range(0.4..1.2), rng.gen_range(0.6..1.8));
+
+        parent.spawn((
+            Mesh3d(assets.rock_mesh.clone()),
+            MeshMaterial3d(assets.rock_material.clone()),
+            Transform::from_translation(position)
+                .with_rotation(Quat::from_rotation_y(rng.gen_range(0.0..TAU)))
+                .with_scale(unsquash * size),
+        ));
+    }
+}
+
+// Height of an island's terrain at `distance` from its center, in unscaled units: a
+// rounded hill rising from a beach ring, which slopes down into a shelf below the water
+fn island_height(kind: IslandKind, distance: f32) -> f32 {
+    let t = distance / kind.footprint_radius();
+    if t >= 1.0 {
+        return -ISLAND_SHELF_DEPTH * ((t - 1.0) / ISLAND_SHELF_WIDTH).min(1.0);
+    }
+
+    let beach = ISLAND_BEACH_HEIGHT * ((1.0 - t) / (1.0 - ISLAND_BEACH_START)).min(1.0);
+    let inland = (1.0 - t / ISLAND_BEACH_START).max(0.0);
+    let hill = (kind.top_height() - ISLAND_BEACH_HEIGHT) * inland * inland * (3.0 - 2.0 * inland);
+    beach + hill
+}
+
+// Terrain mesh and matching heightfield collider for an island kind, centered on the
+// island's origin at the waterline. Both sample `island_height` on the same square grid
+// of `resolution` points per side, and the mesh is colored sand along the beach and by
+// kind above it.
+pub fn island_shape(kind: IslandKind, resolution: usize) -> (Mesh, Collider) {
+    // The grid reaches past the beach so the shelf hides the island's edge under water
+    let half_size = kind.footprint_radius() * (1.0 + ISLAND_SHELF_WIDTH);
+    let step = 2.0 * half_size / (resolution - 1) as f32;
+    let land_color = match kind {
+        IslandKind::Atoll => LinearRgba::from(Color::srgb(0.2, 0.7, 0.25)), // Palm scrub
+        IslandKind::Rocky => LinearRgba::from(Color::srgb(0.45, 0.42, 0.4)), // Bare rock
+        IslandKind::Sloped => LinearRgba::from(Color::srgb(0.1, 0.8, 0.2)), // Grass
+    };
+    let sand_color = LinearRgba::from(Color::srgb(0.9, 0.82, 0.6));
+
+    let mut heights = vec![vec![0.0; resolution]; resolution];
+    let mut positions = Vec::with_capacity(resolution * resolution);
+    let mut colors = Vec::with_capacity(resolution * resolution);
+    for (row, row_heights) in heights.iter_mut().enumerate() {
+        for (column, height) in row_heights.iter_mut().enumerate() {
+            let x = -half_size + column as f32 * step;
+            let z = -half_size + row as f32 * step;
+            *height = island_height(kind, Vec2::new(x, z).length());
+            positions.push([x, *height, z]);
+
+            // Sand up to just above the beach, then blend into the kind's cover
+            let land = ((*height - ISLAND_BEACH_HEIGHT) / ISLAND_BEACH_HEIGHT).clamp(0.0, 1.0);
+            colors.push(sand_color.mix(&land_color, land).to_f32_array());
+        }
+    }
+
+    let mut indices = Vec::with_capacity((resolution - 1) * (resolution - 1) * 6);
+    for row in 0..resolution - 1 {
+        for column in 0..resolution - 1 {
+            let corner = (row * resolution + column) as u32;
+            let below = corner + resolution as u32;
+            indices.extend_from_slice(&[corner, below, corner + 1, corner + 1, below, below + 1]);
+        }
+    }
+
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::default())
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, colors)
+        .with_inserted_indices(Indices::U32(indices));
+    mesh.compute_smooth_normals();
+
+    // The profile is round, so it doesn't matter which grid axis the heightfield reads as rows
+    let collider = Collider::heightfield(heights, Vec3::new(2.0 * half_size, 1.0, 2.0 * half_size));
+    (mesh, collider)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Transforms of everything spawned for `count` islands from `seed`, islands and their
+    // props alike, in spawn order
+    fn island_transforms(seed: u64, count: usize) -> Vec<Transform> {
+        let mut world = World::new();
+        let mut meshes = Assets::<Mesh>::default();
+        let mut materials = Assets::<StandardMaterial>::default();
+        let mut images = Assets::<Image>::default();
+        let world_assets = WorldAssets::new(&mut meshes, &mut materials, &mut images, true);
+        let mut world_seed = WorldSeed::new(seed);
+
+        let mut commands = world.commands();
+        for _ in 0..count {
+            spawn_island(&mut commands, &world_assets, -500.0..500.0, -500.0..500.0, &mut world_seed.rng);
+        }
+        world.flush();
+
+        let mut query = world.query::<(Entity, &Transform)>();
+        let mut spawned: Vec<(Entity, Transform)> = query.iter(&world).map(|(entity, transform)| (entity, *transform)).collect();
+        spawned.sort_by_key(|(entity, _)| *entity);
+        spawned.into_iter().map(|(_, transform)| transform).collect()
+    }
+
+    #[test]
+    fn same_seed_builds_the_same_islands() {
+        let islands = island_transforms(42, 8);
+        assert!(islands.len() > 8);
+        assert_eq!(island_transforms(42, 8), islands);
+        assert_ne!(island_transforms(43, 8), islands);
+    }
 }
@@ -0,0 +1,9 @@
+use bevy::prelude::*;
+
+#[derive(States, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameState {
+    #[default]
+    MainMenu, // Start screen; the world is only built when leaving it
+    Playing,
+    Paused, // Physics, controls and the camera are frozen; toggled with Escape
+}
@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+use crate::resources::{Records, Stats};
+
+pub const STATS_FILE: &str = "stats.json";
+
+// Read the all-time records saved by earlier sessions. A missing file starts from zero,
+// and so does one that can't be read or parsed, after a warning.
+pub fn load_stats(mut stats: ResMut<Stats>) {
+    let contents = match std::fs::read_to_string(STATS_FILE) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return,
+        Err(err) => {
+            warn!("Ignoring {}: {}", STATS_FILE, err);
+            return;
+        }
+    };
+
+    match serde_json::from_str::<Records>(&contents) {
+        Ok(records) => stats.all_time = records,
+        Err(err) => warn!("Ignoring {}: {}", STATS_FILE, err),
+    }
+}
+
+// Write the all-time records out as the app exits, however it was closed. Quitting from
+// inside the game has already saved by then; this catches the window being closed.
+pub fn save_stats(mut exit_events: EventReader<AppExit>, stats: Res<Stats>) {
+    if exit_events.read().last().is_none() {
+        return;
+    }
+    write_stats(&stats);
+}
+
+// Write the all-time records to STATS_FILE, logging rather than failing if that can't be done
+pub fn write_stats(stats: &Stats) {
+    let saved = serde_json::to_string_pretty(&stats.all_time)
+        .map_err(|err| err.to_string())
+        .and_then(|json| std::fs::write(STATS_FILE, json).map_err(|err| err.to_string()));
+    if let Err(err) = saved {
+        error!("Cannot save {}: {}", STATS_FILE, err);
+    }
+}
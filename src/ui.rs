@@ -1,12 +1,45 @@
 use bevy::prelude::*;
 use avian3d::prelude::*;
-use crate::components::{FlightDataText, ControlsText, Plane, Water};
-use crate::resources::PlaneState;
+use crate::components::{
+    FlightDataText, ControlsText, Plane, Water, FollowCamera, QuitDialogPanel, QuitDialogText,
+    FlightPathMarker, BoresightMarker, AirspeedText, AltimeterText, HeadingText, DebugOverlayPanel,
+    DebugOverlayText, PausePanel, RecordsText, LandingBanner, LandingBannerText, GForceVignette,
+};
+use crate::resources::{PlaneState, QuitDialog, QuitSettings, KeyBindings, HudDisplay, AutoThrottle, FlightConfig, Fuel, Wind, Stats, LandingRating, WaterState};
 use crate::constants::*;
+use crate::util::{world_to_screen, compass_bearing, cardinal_direction, smoothing};
+use crate::rings::{RingCourse, Score};
+use crate::game_modes::LandingChallenge;
+use crate::replay::FlightRecorder;
+use crate::plane_systems::takeoff_ready;
 
-pub fn setup_ui(commands: &mut Commands, asset_server: &Res<AssetServer>) {
+const WARNING_RED: Color = Color::srgb(1.0, 0.25, 0.2);
+const WARNING_YELLOW: Color = Color::srgb(1.0, 0.85, 0.2);
+
+pub fn setup_ui(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    key_bindings: &KeyBindings,
+    quit_settings: &QuitSettings,
+) {
     let font = asset_server.load("fonts/FiraMono-Medium.ttf");
-    
+
+    // Blackout/redout overlay, spawned first so the HUD stays readable on top of it.
+    // The thick border darkens the edges more than the middle, like a vignette.
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            border: UiRect::all(Val::Percent(G_VIGNETTE_BORDER)),
+            display: Display::None,
+            ..default()
+        },
+        BackgroundColor(Color::NONE),
+        BorderColor(Color::NONE),
+        GForceVignette,
+    ));
+
     // Flight data panel (left side)
     let flight_data_entity = commands
         .spawn((
@@ -17,7 +50,7 @@ pub fn setup_ui(commands: &mut Commands, asset_server: &Res<AssetServer>) {
                 padding: UiRect::all(Val::Px(10.0)),
                 ..default()
             },
-            BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.7)),
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
         ))
         .id();
         
@@ -27,7 +60,10 @@ pub fn setup_ui(commands: &mut Commands, asset_server: &Res<AssetServer>) {
             Text::new("FLIGHT DATA\n\
              Airspeed: 0 km/h (0%)\n\
              Altitude: 0.0 m\n\
-             Status: ON WATER\n\
+             Health: 100%\n\
+             Fuel: 100%\n\
+             Wind: calm\n\
+             Status: LANDED\n\
              Momentum: 0.0, 0.0, 0.0\n\
              Impact Bounce: 0.0\n"),
             TextFont {
@@ -35,7 +71,7 @@ pub fn setup_ui(commands: &mut Commands, asset_server: &Res<AssetServer>) {
                 font_size: 20.0,
                 ..default()
             },
-            TextColor(Color::WHITE.into()),
+            TextColor(Color::WHITE),
             FlightDataText,
         ))
         .id();
@@ -46,7 +82,7 @@ pub fn setup_ui(commands: &mut Commands, asset_server: &Res<AssetServer>) {
     // Add text entity to panel
     commands.entity(flight_data_entity).add_child(flight_data_text_entity);
     
-    // Controls panel (right side)
+    // Controls panel (right side), with the records below the controls
     let controls_panel_entity = commands
         .spawn((
             Node {
@@ -54,9 +90,10 @@ pub fn setup_ui(commands: &mut Commands, asset_server: &Res<AssetServer>) {
                 top: Val::Px(10.0),
                 right: Val::Px(10.0),
                 padding: UiRect::all(Val::Px(10.0)),
+                flex_direction: FlexDirection::Column,
                 ..default()
             },
-            BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.7)),
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
         ))
         .id();
         
@@ -65,6 +102,7 @@ pub fn setup_ui(commands: &mut Commands, asset_server: &Res<AssetServer>) {
         .spawn((
             Text::new("CONTROLS\n\
              Pitch: 0.0°\n\
+             Trim: 0%\n\
              Roll: 0.0°\n\
              Yaw: 0.0°\n\
              Bank Angle: 0.0°\n\
@@ -75,7 +113,7 @@ pub fn setup_ui(commands: &mut Commands, asset_server: &Res<AssetServer>) {
                 font_size: 20.0,
                 ..default()
             },
-            TextColor(Color::WHITE.into()),
+            TextColor(Color::WHITE),
             ControlsText,
         ))
         .id();
@@ -85,6 +123,20 @@ pub fn setup_ui(commands: &mut Commands, asset_server: &Res<AssetServer>) {
     
     // Add text entity to panel
     commands.entity(controls_panel_entity).add_child(controls_text_entity);
+
+    let records_text_entity = commands
+        .spawn((
+            Text::new("RECORDS\n"),
+            TextFont {
+                font: font.clone(),
+                font_size: 20.0,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+            RecordsText,
+        ))
+        .id();
+    commands.entity(controls_panel_entity).add_child(records_text_entity);
         
     // Flight controls help panel (bottom left)
     let help_panel_entity = commands
@@ -96,24 +148,20 @@ pub fn setup_ui(commands: &mut Commands, asset_server: &Res<AssetServer>) {
                 padding: UiRect::all(Val::Px(10.0)),
                 ..default()
             },
-            BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.7)),
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
         ))
         .id();
         
     // Add text entity as a child
     let help_text_entity = commands
         .spawn((
-            Text::new("Flight Controls:\n\
-             W/S: Pitch\n\
-             A/D: Roll\n\
-             Q/E: Yaw\n\
-             Up/Down: Thrust\n"),
+            Text::new(help_text(key_bindings, quit_settings)),
             TextFont {
                 font: font.clone(),
                 font_size: 20.0,
                 ..default()
             },
-            TextColor(Color::WHITE.into()),
+            TextColor(Color::WHITE),
         ))
         .id();
     
@@ -122,10 +170,286 @@ pub fn setup_ui(commands: &mut Commands, asset_server: &Res<AssetServer>) {
     
     // Add text entity to panel
     commands.entity(help_panel_entity).add_child(help_text_entity);
+
+    // Airspeed (left) and altitude (right) readouts at mid-height, colored by update_flight_readouts
+    spawn_readout(commands, &font, UiRect { left: Val::Px(10.0), ..default() }, AirspeedText);
+    spawn_readout(commands, &font, UiRect { right: Val::Px(10.0), ..default() }, AltimeterText);
+
+    // Compass heading (top center)
+    commands
+        .spawn(Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        })
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        padding: UiRect::all(Val::Px(8.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new("HDG 000 N"),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 24.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                        HeadingText,
+                    ));
+                });
+        });
+
+    // Landing rating (upper middle, shown for a few seconds after touching down)
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(25.0),
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                display: Display::None,
+                ..default()
+            },
+            LandingBanner,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        padding: UiRect::all(Val::Px(10.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new(""),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 32.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                        LandingBannerText,
+                    ));
+                });
+        });
+
+    // Boresight marker (where the nose points)
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Px(BORESIGHT_MARKER_SIZE),
+            height: Val::Px(BORESIGHT_MARKER_SIZE),
+            display: Display::None,
+            ..default()
+        },
+        BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.8)),
+        BoresightMarker,
+    ));
+
+    // Flight path marker (where the plane is actually going)
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Px(FLIGHT_PATH_MARKER_SIZE),
+            height: Val::Px(FLIGHT_PATH_MARKER_SIZE),
+            border: UiRect::all(Val::Px(2.0)),
+            display: Display::None,
+            ..default()
+        },
+        BorderColor(Color::srgb(0.2, 1.0, 0.3)),
+        BorderRadius::MAX,
+        FlightPathMarker,
+    ));
+
+    // Debug overlay (bottom center, hidden until F3 is pressed)
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(10.0),
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                display: Display::None,
+                ..default()
+            },
+            DebugOverlayPanel,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        padding: UiRect::all(Val::Px(8.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new(""),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 16.0,
+                            ..default()
+                        },
+                        TextColor(Color::srgb(0.6, 1.0, 0.6)),
+                        DebugOverlayText,
+                    ));
+                });
+        });
+
+    // Pause overlay (centered, shown while paused). Spawned before the quit dialog so
+    // the dialog draws on top when opened from here.
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                display: Display::None,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.3)),
+            PausePanel,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        padding: UiRect::all(Val::Px(20.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new("PAUSED\n\n\
+                         Escape: Resume\n\
+                         Q: Quit\n"),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 24.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
+        });
+
+    // Quit confirmation dialog (centered, hidden until the exit key is pressed)
+    let quit_dialog_entity = commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                display: Display::None,
+                ..default()
+            },
+            QuitDialogPanel,
+        ))
+        .id();
+
+    let quit_panel_entity = commands
+        .spawn((
+            Node {
+                padding: UiRect::all(Val::Px(20.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+        ))
+        .id();
+
+    let quit_text_entity = commands
+        .spawn((
+            Text::new(quit_dialog_text(false)),
+            TextFont {
+                font: font.clone(),
+                font_size: 24.0,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+            QuitDialogText,
+        ))
+        .id();
+
+    commands.entity(quit_panel_entity).add_child(quit_text_entity);
+    commands.entity(quit_dialog_entity).add_child(quit_panel_entity);
+}
+
+fn quit_dialog_text(quit_selected: bool) -> String {
+    let (quit, stay) = if quit_selected {
+        ("[ Quit ]", "  Keep flying  ")
+    } else {
+        ("  Quit  ", "[ Keep flying ]")
+    };
+
+    format!(
+        "Quit? Unsaved progress will be lost.\n\n\
+         {}   {}\n\n\
+         Left/Right: Choose  Enter: Confirm\n\
+         Y: Quit  N: Keep flying\n\
+         Hold the exit key to force quit\n",
+        quit,
+        stay
+    )
+}
+
+pub fn show_pause_overlay(mut panel_query: Query<&mut Node, With<PausePanel>>) {
+    for mut node in panel_query.iter_mut() {
+        node.display = Display::Flex;
+    }
 }
 
+pub fn hide_pause_overlay(mut panel_query: Query<&mut Node, With<PausePanel>>) {
+    for mut node in panel_query.iter_mut() {
+        node.display = Display::None;
+    }
+}
+
+pub fn update_quit_dialog(
+    quit_dialog: Res<QuitDialog>,
+    mut dialog_query: Query<&mut Node, With<QuitDialogPanel>>,
+    mut text_query: Query<&mut Text, With<QuitDialogText>>,
+) {
+    if !quit_dialog.is_changed() {
+        return;
+    }
+
+    for mut node in dialog_query.iter_mut() {
+        node.display = if quit_dialog.open { Display::Flex } else { Display::None };
+    }
+
+    for mut text in text_query.iter_mut() {
+        text.0 = quit_dialog_text(quit_dialog.quit_selected);
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn update_ui_display(
     plane_state: Res<PlaneState>,
+    flight_config: Res<FlightConfig>,
+    auto_throttle: Res<AutoThrottle>,
+    fuel: Res<Fuel>,
+    wind: Res<Wind>,
+    ring_course: Option<Res<RingCourse>>,
+    score: Res<Score>,
+    recorder: Res<FlightRecorder>,
+    mut hud: ResMut<HudDisplay>,
+    time: Res<Time>,
     plane_query: Query<&Transform, With<Plane>>,
     water_query: Query<Entity, With<Water>>,
     colliding_entities_query: Query<&CollidingEntities, With<Plane>>,
@@ -134,41 +458,125 @@ pub fn update_ui_display(
         Query<&mut Text, With<ControlsText>>,
     )>,
 ) {
-    let plane_transform = plane_query.single();
-    let colliding_entities = colliding_entities_query.single();
-    let water_entity = water_query.single();
-    let is_on_water = colliding_entities.contains(&water_entity);
+    let (Ok(plane_transform), Ok(colliding_entities)) =
+        (plane_query.get_single(), colliding_entities_query.get_single())
+    else {
+        return;
+    };
+    let is_on_water = water_query
+        .get_single()
+        .is_ok_and(|water_entity| colliding_entities.contains(&water_entity));
     
     // Get rotation as Euler angles
     let (pitch, yaw, roll) = plane_transform.rotation.to_euler(EulerRot::XYZ);
     
     // Check takeoff conditions
-    let has_takeoff_speed = plane_state.speed > MAX_AIRSPEED * TAKEOFF_SPEED_THRESHOLD;
-    let has_positive_pitch = pitch < -0.1;
-    let takeoff_ready = has_takeoff_speed && has_positive_pitch;
+    let takeoff_ready = takeoff_ready(&plane_state, &flight_config, plane_transform);
+
+    // Ease the displayed numbers toward the real values
+    let altitude = plane_transform.translation.y;
+    if hud.initialized {
+        let alpha = smoothing(HUD_SMOOTHING_TIME, time.delta_secs());
+        hud.speed = hud.speed.lerp(plane_state.speed, alpha);
+        hud.altitude = hud.altitude.lerp(altitude, alpha);
+        hud.momentum = hud.momentum.lerp(plane_state.momentum, alpha);
+        hud.impact_bounce = hud.impact_bounce.lerp(plane_state.impact_bounce, alpha);
+        hud.bank_angle = hud.bank_angle.lerp(plane_state.bank_angle, alpha);
+    } else {
+        hud.initialized = true;
+        hud.speed = plane_state.speed;
+        hud.altitude = altitude;
+        hud.momentum = plane_state.momentum;
+        hud.impact_bounce = plane_state.impact_bounce;
+        hud.bank_angle = plane_state.bank_angle;
+    }
     
     // Update flight data text
     if let Ok(mut flight_data_text) = text_queries.p0().get_single_mut() {
-        let status_str = if is_on_water { "ON WATER" } else { "AIRBORNE" };
+        let status_str = if plane_state.crashed {
+            "CRASHED (R to reset)"
+        } else if is_on_water {
+            match plane_state.water_state {
+                WaterState::Landing => "LANDED",
+                WaterState::Sailing => "SAILING",
+                WaterState::Stopped => "STOPPED",
+                WaterState::TakingOff => "TAKEOFF RUN",
+            }
+        } else if plane_state.stalling {
+            "STALL"
+        } else if plane_state.in_ground_effect {
+            "GROUND EFFECT"
+        } else {
+            "AIRBORNE"
+        };
+        // Wind is reported by the direction it blows from
+        let wind_from = compass_bearing(-wind.direction)
+            .map_or("calm", cardinal_direction);
+        let auto_throttle_str = if auto_throttle.enabled {
+            format!("AUTOTHR {:.0} km/h", auto_throttle.target_speed)
+        } else {
+            "OFF".to_string()
+        };
+        let autopilot_str = if plane_state.autopilot {
+            format!("HOLD {:.0} m", plane_state.autopilot_altitude)
+        } else {
+            "OFF".to_string()
+        };
         
         // In the new Bevy API, Text is a tuple struct with a single String field
         // Update the text content directly
         flight_data_text.0 = format!(
             "FLIGHT DATA\n\
              Airspeed: {:.1} km/h ({:.0}%)\n\
+             Auto-throttle: {}\n\
+             Autopilot: {}\n\
              Altitude: {:.1} m\n\
+             G-force: {:.1} G\n\
+             Health: {:.0}%\n\
+             Fuel: {:.0}%{}\n\
+             Boost: {:.0}%{}\n\
+             Wind: {} at {:.0} km/h\n\
              Status: {}\n\
              Momentum: {:.1}, {:.1}, {:.1}\n\
              Impact Bounce: {:.1}\n",
-            plane_state.speed,
-            (plane_state.speed / MAX_AIRSPEED) * 100.0,
-            plane_transform.translation.y,
+            hud.speed,
+            (hud.speed / flight_config.max_airspeed) * 100.0,
+            auto_throttle_str,
+            autopilot_str,
+            hud.altitude,
+            plane_state.g_force,
+            plane_state.health / MAX_HEALTH * 100.0,
+            fuel.amount / flight_config.fuel_capacity * 100.0,
+            if fuel.amount <= 0.0 { " (ENGINE OUT)" } else { "" },
+            plane_state.boost_energy * 100.0,
+            if plane_state.boosting { " BOOST" } else { "" },
+            wind_from,
+            wind.strength,
             status_str,
-            plane_state.momentum.x,
-            plane_state.momentum.y,
-            plane_state.momentum.z,
-            plane_state.impact_bounce
+            hud.momentum.x,
+            hud.momentum.y,
+            hud.momentum.z,
+            hud.impact_bounce
         );
+
+        if plane_state.returning_to_area {
+            flight_data_text.0.push_str("RETURN TO AREA\n");
+        }
+
+        if let Some(playback) = &recorder.playback {
+            flight_data_text.0.push_str(&format!("Replay: {:.1} km/h\n", playback.speed));
+        } else if recorder.recording {
+            flight_data_text.0.push_str(&format!("Recording: {:.0} s\n", recorder.duration()));
+        }
+
+        // Ring course progress, when there is a course to fly
+        if let Some(course) = &ring_course {
+            flight_data_text.0.push_str(&format!(
+                "Score: {}  Rings left: {}\n",
+                score.0,
+                course.remaining()
+            ));
+        }
     }
     
     // Update controls text
@@ -177,17 +585,360 @@ pub fn update_ui_display(
         controls_text.0 = format!(
             "CONTROLS\n\
              Pitch: {:.1}°\n\
+             Trim: {:+.0}%\n\
              Roll: {:.1}°\n\
              Yaw: {:.1}°\n\
              Bank Angle: {:.1}°\n\
              Thrust: {:.0}%\n\
              Takeoff Ready: {}\n",
             pitch.to_degrees(),
+            plane_state.trim_pitch * 100.0,
             roll.to_degrees(),
             yaw.to_degrees(),
-            plane_state.bank_angle.to_degrees(),
-            (plane_state.speed / MAX_AIRSPEED) * 100.0,
+            hud.bank_angle.to_degrees(),
+            (hud.speed / flight_config.max_airspeed) * 100.0,
             if takeoff_ready { "YES" } else { "NO" }
         );
     }
 }
+
+// Best altitude and airspeed of this session, with the all-time bests beside them
+pub fn update_records_display(
+    stats: Res<Stats>,
+    mut text_query: Query<&mut Text, With<RecordsText>>,
+) {
+    if !stats.is_changed() {
+        return;
+    }
+    let Ok(mut text) = text_query.get_single_mut() else {
+        return;
+    };
+
+    text.0 = format!(
+        "RECORDS (session / all-time)\n\
+         Altitude: {:.0} / {:.0} m\n\
+         Airspeed: {:.0} / {:.0} km/h\n",
+        stats.session.max_altitude,
+        stats.all_time.max_altitude,
+        stats.session.max_speed,
+        stats.all_time.max_speed,
+    );
+}
+
+// Fade the view toward black under sustained positive G and toward red under negative G,
+// edges first
+pub fn update_g_vignette(
+    plane_state: Res<PlaneState>,
+    mut vignette_query: Query<(&mut Node, &mut BackgroundColor, &mut BorderColor), With<GForceVignette>>,
+) {
+    let Ok((mut node, mut background, mut border)) = vignette_query.get_single_mut() else {
+        return;
+    };
+
+    let strain = plane_state.g_strain;
+    if strain == 0.0 {
+        node.display = Display::None;
+        return;
+    }
+    let color = if strain > 0.0 { Color::BLACK } else { Color::srgb(0.6, 0.0, 0.0) };
+    let intensity = strain.abs();
+    border.0 = color.with_alpha(intensity.sqrt());
+    background.0 = color.with_alpha(intensity * intensity);
+    node.display = Display::Flex;
+}
+
+// Put up the rating of a landing as it happens and take it down once its time is up.
+// In landing mode the banner adds the touchdown's score against the target.
+pub fn update_landing_banner(
+    mut landing_rating: ResMut<LandingRating>,
+    landing_challenge: Option<Res<LandingChallenge>>,
+    time: Res<Time>,
+    mut banner_query: Query<&mut Node, With<LandingBanner>>,
+    mut text_query: Query<(&mut Text, &mut TextColor), With<LandingBannerText>>,
+) {
+    let (Ok(mut banner), Ok((mut text, mut text_color))) =
+        (banner_query.get_single_mut(), text_query.get_single_mut())
+    else {
+        return;
+    };
+
+    landing_rating.banner_time = (landing_rating.banner_time - time.delta_secs()).max(0.0);
+    let Some(rating) = landing_rating.last.filter(|_| landing_rating.banner_time > 0.0) else {
+        banner.display = Display::None;
+        return;
+    };
+
+    let (verdict, color) = if rating >= LANDING_SMOOTH_SCORE {
+        ("SMOOTH LANDING", Color::WHITE)
+    } else if rating >= LANDING_ROUGH_SCORE {
+        ("LANDING", WARNING_YELLOW)
+    } else {
+        ("ROUGH!", WARNING_RED)
+    };
+    text.0 = format!("{} {:.0}  (best {:.0})", verdict, rating, landing_rating.best);
+    if let Some(challenge) = landing_challenge
+        && let Some(last) = challenge.last
+    {
+        text.0 += &format!("\nTARGET {:.0} at {:.0} m  (best {:.0})", last.score, last.distance, challenge.best);
+    }
+    text_color.0 = color;
+    banner.display = Display::Flex;
+}
+
+// Short name of a key for the help panel: "W" rather than "KeyW", "Up" rather than "ArrowUp"
+fn key_name(key: KeyCode) -> String {
+    let name = format!("{:?}", key);
+    ["Key", "Digit", "Arrow"]
+        .iter()
+        .find_map(|prefix| name.strip_prefix(prefix))
+        .unwrap_or(&name)
+        .to_string()
+}
+
+// The controls help, with the flight keys and exit key as they're currently bound
+fn help_text(key_bindings: &KeyBindings, quit_settings: &QuitSettings) -> String {
+    let pair = |first: KeyCode, second: KeyCode| format!("{}/{}", key_name(first), key_name(second));
+    format!(
+        "Flight Controls:\n\
+         {}: Pitch  {}: Roll  {}: Yaw\n\
+         {}: Throttle  {}: Trim\n\
+         {}: Boost  T: Auto-throttle  L: Autopilot\n\
+         M: Mouse flight  Space: Fire  R: Reset\n\
+         C: Camera mode  V: Free look  F6: Spectator\n\
+         F3: Debug  F4: Zero-G  F9: Turbulence\n\
+         F7/F8: Record/Replay  F12: Screenshot\n\
+         {}: Pause (hold to quit)",
+        pair(key_bindings.pitch_down, key_bindings.pitch_up),
+        pair(key_bindings.roll_left, key_bindings.roll_right),
+        pair(key_bindings.yaw_left, key_bindings.yaw_right),
+        pair(key_bindings.throttle_up, key_bindings.throttle_down),
+        pair(key_bindings.trim_up, key_bindings.trim_down),
+        key_name(key_bindings.boost),
+        key_name(quit_settings.exit_key),
+    )
+}
+
+// A single large number in its own panel, pinned to one side of the screen
+fn spawn_readout(commands: &mut Commands, font: &Handle<Font>, side: UiRect, marker: impl Component) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(50.0),
+                left: side.left,
+                right: side.right,
+                padding: UiRect::all(Val::Px(8.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 28.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                marker,
+            ));
+        });
+}
+
+// Update the airspeed and altitude readouts, coloring them as warnings:
+// airspeed yellow near the minimum and red when stalled, altitude red when low and airborne
+#[allow(clippy::type_complexity)]
+pub fn update_flight_readouts(
+    plane_state: Res<PlaneState>,
+    flight_config: Res<FlightConfig>,
+    hud: Res<HudDisplay>,
+    plane_query: Query<(&Transform, &CollidingEntities), With<Plane>>,
+    water_query: Query<Entity, With<Water>>,
+    mut airspeed_query: Query<(&mut Text, &mut TextColor), (With<AirspeedText>, Without<AltimeterText>)>,
+    mut altimeter_query: Query<(&mut Text, &mut TextColor), (With<AltimeterText>, Without<AirspeedText>)>,
+) {
+    let Ok((plane_transform, colliding_entities)) = plane_query.get_single() else {
+        return;
+    };
+    let is_on_water = water_query
+        .get_single()
+        .is_ok_and(|water_entity| colliding_entities.contains(&water_entity));
+
+    if let Ok((mut text, mut color)) = airspeed_query.get_single_mut() {
+        text.0 = format!("SPD {:>4.0}", hud.speed);
+        color.0 = if plane_state.stalling {
+            WARNING_RED
+        } else if plane_state.speed < flight_config.min_airspeed * LOW_AIRSPEED_MARGIN {
+            WARNING_YELLOW
+        } else {
+            Color::WHITE
+        };
+    }
+
+    if let Ok((mut text, mut color)) = altimeter_query.get_single_mut() {
+        text.0 = format!("ALT {:>5.1}", hud.altitude);
+        color.0 = if !is_on_water && plane_transform.translation.y < LOW_ALTITUDE_WARNING {
+            WARNING_RED
+        } else {
+            Color::WHITE
+        };
+    }
+}
+
+// Show the compass heading of the plane's nose. Holds the last heading while
+// pointing almost straight up or down, where the bearing is meaningless.
+pub fn update_heading_display(
+    plane_query: Query<&Transform, With<Plane>>,
+    mut heading_query: Query<&mut Text, With<HeadingText>>,
+    mut last_bearing: Local<f32>,
+) {
+    let (Ok(plane_transform), Ok(mut heading_text)) =
+        (plane_query.get_single(), heading_query.get_single_mut())
+    else {
+        return;
+    };
+
+    if let Some(bearing) = compass_bearing(*plane_transform.forward()) {
+        *last_bearing = bearing;
+    }
+
+    // Round first so 359.6 shows as 000 rather than 360
+    let bearing = last_bearing.round() % 360.0;
+    heading_text.0 = format!("HDG {:03.0} {}", bearing, cardinal_direction(bearing));
+}
+
+// Place a square HUD marker over a world direction from the plane, clamped to the screen edges.
+// Hidden when the direction points behind the camera.
+fn place_hud_marker(
+    node: &mut Node,
+    size: f32,
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    target: Vec3,
+) {
+    let (Some(screen_pos), Some(viewport_size)) = (
+        world_to_screen(camera, camera_transform, target),
+        camera.logical_viewport_size(),
+    ) else {
+        node.display = Display::None;
+        return;
+    };
+
+    let half_size = size / 2.0;
+    let clamped = screen_pos.clamp(Vec2::splat(half_size), viewport_size - half_size);
+    node.left = Val::Px(clamped.x - half_size);
+    node.top = Val::Px(clamped.y - half_size);
+    node.display = Display::Flex;
+}
+
+#[allow(clippy::type_complexity)]
+pub fn update_hud_markers(
+    plane_query: Query<(&Transform, &LinearVelocity), With<Plane>>,
+    camera_query: Query<(&Camera, &Transform), With<FollowCamera>>,
+    mut marker_queries: ParamSet<(
+        Query<&mut Node, With<FlightPathMarker>>,
+        Query<&mut Node, With<BoresightMarker>>,
+    )>,
+) {
+    let (Ok((plane_transform, linear_vel)), Ok((camera, camera_transform))) =
+        (plane_query.get_single(), camera_query.get_single())
+    else {
+        return;
+    };
+
+    // Use this frame's camera transform rather than the last propagated GlobalTransform
+    // so the markers don't lag a frame behind the view
+    let camera_global = GlobalTransform::from(*camera_transform);
+    let origin = plane_transform.translation;
+
+    if let Ok(mut node) = marker_queries.p0().get_single_mut() {
+        match linear_vel.0.try_normalize() {
+            Some(direction) if linear_vel.0.length() > FLIGHT_PATH_MARKER_MIN_SPEED => {
+                let target = origin + direction * HUD_MARKER_DISTANCE;
+                place_hud_marker(&mut node, FLIGHT_PATH_MARKER_SIZE, camera, &camera_global, target);
+            }
+            _ => node.display = Display::None,
+        }
+    }
+
+    if let Ok(mut node) = marker_queries.p1().get_single_mut() {
+        let target = origin + plane_transform.forward() * HUD_MARKER_DISTANCE;
+        place_hud_marker(&mut node, BORESIGHT_MARKER_SIZE, camera, &camera_global, target);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Headless app running the HUD text systems against a plane at `height`
+    fn hud_app(height: f32) -> App {
+        let flight_config = FlightConfig::default();
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(PlaneState::new(&flight_config))
+            .insert_resource(Fuel { amount: flight_config.fuel_capacity })
+            .insert_resource(flight_config)
+            .init_resource::<AutoThrottle>()
+            .init_resource::<Wind>()
+            .init_resource::<Score>()
+            .init_resource::<FlightRecorder>()
+            .init_resource::<HudDisplay>()
+            .add_systems(Update, (update_ui_display, update_flight_readouts).chain());
+        app.world_mut().spawn((Plane, Transform::from_xyz(0.0, height, 0.0), CollidingEntities::default()));
+        app.world_mut().spawn((Text::default(), FlightDataText));
+        app.world_mut().spawn((Text::default(), ControlsText));
+        app.world_mut().spawn((Text::default(), TextColor::default(), AirspeedText));
+        app.world_mut().spawn((Text::default(), TextColor::default(), AltimeterText));
+        app
+    }
+
+    fn text_of<T: Component>(app: &mut App) -> (String, Color) {
+        let world = app.world_mut();
+        let mut query = world.query_filtered::<(&Text, Option<&TextColor>), With<T>>();
+        let (text, color) = query.single(world);
+        (text.0.clone(), color.map_or(Color::WHITE, |color| color.0))
+    }
+
+    #[test]
+    fn hud_text_follows_the_flight_state() {
+        let mut app = hud_app(50.0);
+        app.world_mut().resource_mut::<PlaneState>().speed = 120.0;
+        app.update();
+
+        let (flight_data, _) = text_of::<FlightDataText>(&mut app);
+        assert!(flight_data.starts_with("FLIGHT DATA\n"));
+        assert!(flight_data.contains("Airspeed: 120.0 km/h"));
+        assert!(flight_data.contains("Altitude: 50.0 m"));
+        assert!(flight_data.contains("Status: AIRBORNE"));
+        assert!(text_of::<ControlsText>(&mut app).0.starts_with("CONTROLS\n"));
+        assert_eq!(text_of::<AirspeedText>(&mut app), ("SPD  120".to_string(), Color::WHITE));
+        assert_eq!(text_of::<AltimeterText>(&mut app), ("ALT  50.0".to_string(), Color::WHITE));
+    }
+
+    #[test]
+    fn help_lists_the_keys_as_bound() {
+        let help = help_text(&KeyBindings::default(), &QuitSettings::default());
+        assert!(help.contains("W/S: Pitch  A/D: Roll  Q/E: Yaw"));
+        assert!(help.contains("Up/Down: Throttle  PageUp/PageDown: Trim"));
+        assert!(help.contains("Escape: Pause"));
+
+        let key_bindings = KeyBindings { pitch_down: KeyCode::KeyI, pitch_up: KeyCode::KeyK, ..default() };
+        let quit_settings = QuitSettings { exit_key: KeyCode::F10, ..default() };
+        let help = help_text(&key_bindings, &quit_settings);
+        assert!(help.contains("I/K: Pitch"));
+        assert!(help.contains("F10: Pause"));
+    }
+
+    #[test]
+    fn readouts_warn_when_stalled_and_low() {
+        let mut app = hud_app(LOW_ALTITUDE_WARNING / 2.0);
+        app.world_mut().resource_mut::<PlaneState>().stalling = true;
+        app.update();
+
+        assert!(text_of::<FlightDataText>(&mut app).0.contains("Status: STALL"));
+        assert_eq!(text_of::<AirspeedText>(&mut app).1, WARNING_RED);
+        assert_eq!(text_of::<AltimeterText>(&mut app).1, WARNING_RED);
+    }
+}
@@ -0,0 +1,184 @@
+use bevy::prelude::*;
+use std::time::Duration;
+use crate::constants::{MENU_REPEAT_DELAY, MENU_REPEAT_INTERVAL};
+
+// Project a world position to logical screen coordinates (origin at the top-left).
+// Returns None when the point is behind the camera or the viewport size isn't known yet.
+// Points in front of the camera but outside the view still return their (off-screen)
+// coordinates so HUD markers can clamp them to the screen edge.
+pub fn world_to_screen(
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    world_pos: Vec3,
+) -> Option<Vec2> {
+    // Reject anything on or behind the camera plane up front; the projection
+    // would otherwise mirror it back onto the screen
+    let to_point = world_pos - camera_transform.translation();
+    if to_point.dot(*camera_transform.forward()) <= 0.0 {
+        return None;
+    }
+
+    camera.world_to_viewport(camera_transform, world_pos).ok()
+}
+
+// Compass bearing in degrees (0..360) of a direction: north is -Z, east is +X.
+// None when the direction is too close to vertical to have a meaningful heading.
+pub fn compass_bearing(direction: Vec3) -> Option<f32> {
+    let flat = Vec2::new(direction.x, -direction.z);
+    if flat.length_squared() < 1e-4 {
+        return None;
+    }
+    Some(flat.x.atan2(flat.y).to_degrees().rem_euclid(360.0))
+}
+
+// Nearest of the eight compass points for a bearing in degrees
+pub fn cardinal_direction(bearing: f32) -> &'static str {
+    const POINTS: [&str; 8] = ["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+    POINTS[((bearing / 45.0).round() as usize) % POINTS.len()]
+}
+
+// Turns a held key into a single press followed by repeats, for menu navigation.
+// Fires immediately on press, again after the initial delay, then every repeat interval.
+pub struct InputRepeat {
+    initial_delay: Duration,
+    repeat_interval: Duration,
+    timer: Timer,
+    held: bool,
+}
+
+impl InputRepeat {
+    pub fn new(initial_delay: f32, repeat_interval: f32) -> Self {
+        let initial_delay = Duration::from_secs_f32(initial_delay);
+        Self {
+            initial_delay,
+            repeat_interval: Duration::from_secs_f32(repeat_interval),
+            timer: Timer::new(initial_delay, TimerMode::Once),
+            held: false,
+        }
+    }
+
+    // Returns true on the frames the held input should be acted on
+    pub fn tick(&mut self, pressed: bool, delta: Duration) -> bool {
+        if !pressed {
+            self.held = false;
+            return false;
+        }
+
+        if !self.held {
+            self.held = true;
+            self.timer = Timer::new(self.initial_delay, TimerMode::Once);
+            return true;
+        }
+
+        if self.timer.tick(delta).just_finished() {
+            self.timer = Timer::new(self.repeat_interval, TimerMode::Once);
+            return true;
+        }
+
+        false
+    }
+}
+
+impl Default for InputRepeat {
+    fn default() -> Self {
+        Self::new(MENU_REPEAT_DELAY, MENU_REPEAT_INTERVAL)
+    }
+}
+
+// Value following a `--name value` style launch argument
+pub fn arg_value(name: &str) -> Option<String> {
+    std::env::args().skip_while(|arg| arg != name).nth(1)
+}
+
+// Lerp blend that eases toward a target with the given time constant in seconds,
+// independent of how the time is split into frames
+pub fn smoothing(response_time: f32, dt: f32) -> f32 {
+    1.0 - (-dt / response_time.max(f32::EPSILON)).exp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::render::camera::{camera_system, ManualTextureViews};
+    use bevy::window::{PrimaryWindow, WindowCreated, WindowResized, WindowResolution, WindowScaleFactorChanged};
+
+    // An 800x600 perspective camera at the origin looking down -Z, with its projection
+    // and viewport filled in by Bevy's camera_system as they would be in the game
+    fn test_camera() -> (Camera, GlobalTransform) {
+        let mut app = App::new();
+        app.add_event::<WindowResized>()
+            .add_event::<WindowCreated>()
+            .add_event::<WindowScaleFactorChanged>()
+            .add_event::<AssetEvent<Image>>()
+            .init_resource::<Assets<Image>>()
+            .init_resource::<ManualTextureViews>()
+            .add_systems(Update, camera_system::<Projection>);
+        app.world_mut().spawn((
+            Window {
+                resolution: WindowResolution::new(800.0, 600.0),
+                ..default()
+            },
+            PrimaryWindow,
+        ));
+        let camera = app.world_mut().spawn((Camera::default(), Projection::default())).id();
+        app.update();
+
+        let camera = app.world().get::<Camera>(camera).unwrap().clone();
+        (camera, GlobalTransform::IDENTITY)
+    }
+
+    #[test]
+    fn point_straight_ahead_projects_to_the_center() {
+        let (camera, transform) = test_camera();
+        let screen = world_to_screen(&camera, &transform, Vec3::new(0.0, 0.0, -10.0)).unwrap();
+        assert!(screen.distance(Vec2::new(400.0, 300.0)) < 0.01, "{screen}");
+    }
+
+    #[test]
+    fn screen_y_grows_downward() {
+        let (camera, transform) = test_camera();
+        let above = world_to_screen(&camera, &transform, Vec3::new(0.0, 2.0, -10.0)).unwrap();
+        let right = world_to_screen(&camera, &transform, Vec3::new(2.0, 0.0, -10.0)).unwrap();
+        assert!(above.y < 300.0);
+        assert!(right.x > 400.0);
+    }
+
+    #[test]
+    fn point_behind_the_camera_is_rejected() {
+        let (camera, transform) = test_camera();
+        assert_eq!(world_to_screen(&camera, &transform, Vec3::new(0.0, 0.0, 10.0)), None);
+        assert_eq!(world_to_screen(&camera, &transform, Vec3::new(5.0, 0.0, 0.0)), None);
+    }
+
+    #[test]
+    fn point_outside_the_view_keeps_its_off_screen_position() {
+        let (camera, transform) = test_camera();
+        let screen = world_to_screen(&camera, &transform, Vec3::new(100.0, 0.0, -10.0)).unwrap();
+        assert!(screen.x > 800.0, "{screen}");
+        assert!((screen.y - 300.0).abs() < 0.01);
+    }
+
+    // Fraction of the gap to the target still left after easing through `steps` of `dt`
+    fn gap_left(response_time: f32, dt: f32, steps: usize) -> f32 {
+        (0..steps).fold(1.0, |gap, _| gap * (1.0 - smoothing(response_time, dt)))
+    }
+
+    #[test]
+    fn smoothing_is_the_same_however_the_time_is_split() {
+        for response_time in [0.1, 0.83, 1.66] {
+            let whole = gap_left(response_time, 0.1, 1);
+            assert!((gap_left(response_time, 0.05, 2) - whole).abs() < 1e-6);
+            assert!((gap_left(response_time, 0.1 / 6.0, 6) - whole).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn smoothing_stays_a_valid_blend() {
+        assert_eq!(smoothing(0.5, 0.0), 0.0);
+        assert!(smoothing(0.5, 100.0) <= 1.0);
+        // One time constant closes about 63% of the gap
+        assert!((smoothing(0.5, 0.5) - 0.632).abs() < 0.001);
+        // A zero response time snaps straight to the target instead of dividing by zero
+        assert_eq!(smoothing(0.0, 1.0 / 60.0), 1.0);
+    }
+}
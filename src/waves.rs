@@ -0,0 +1,85 @@
+use bevy::{
+    prelude::*,
+    render::mesh::VertexAttributeValues,
+};
+use crate::constants::*;
+use crate::resources::WorldConfig;
+
+// Marks the water mesh whose vertices are displaced into waves
+#[derive(Component)]
+pub struct WaterWaves;
+
+// (direction, wavelength, relative amplitude, speed) of each swell. The amplitudes sum
+// to 1 so the surface never moves more than WAVE_AMPLITUDE from rest.
+const SWELLS: [(Vec2, f32, f32, f32); 3] = [
+    (Vec2::new(1.0, 0.0), 120.0, 0.5, 6.0),
+    (Vec2::new(0.6, 0.8), 80.0, 0.3, 5.0),
+    (Vec2::new(-0.7, 0.7), 55.0, 0.2, 4.0),
+];
+
+// Height of the animated water surface and its slope along X and Z
+fn wave_sample(x: f32, z: f32, time: f32) -> (f32, Vec2) {
+    let mut height = 0.0;
+    let mut slope = Vec2::ZERO;
+    for (direction, wavelength, amplitude, speed) in SWELLS {
+        let k = std::f32::consts::TAU / wavelength;
+        let phase = k * (direction.dot(Vec2::new(x, z)) - speed * time);
+        let amplitude = amplitude * WAVE_AMPLITUDE;
+        height += amplitude * phase.sin();
+        slope += direction * amplitude * k * phase.cos();
+    }
+    (height, slope)
+}
+
+// Height of the water surface at a point; flat at zero when waves are turned off
+pub fn water_surface_height(world_config: &WorldConfig, x: f32, z: f32, time: f32) -> f32 {
+    if world_config.water_waves {
+        wave_sample(x, z, time).0
+    } else {
+        0.0
+    }
+}
+
+// Displace the water mesh's vertices into rolling swells and fix up its normals
+pub fn animate_waves(
+    time: Res<Time>,
+    world_config: Res<WorldConfig>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    water_query: Query<(&Mesh3d, &Transform), With<WaterWaves>>,
+) {
+    if !world_config.water_waves {
+        return;
+    }
+    let t = time.elapsed_secs();
+
+    // The water can be moved (it follows the plane in the endless ocean), so the swells
+    // are sampled at each vertex's world position
+    for (mesh_handle, transform) in water_query.iter() {
+        let Some(mesh) = meshes.get_mut(&mesh_handle.0) else {
+            continue;
+        };
+
+        let samples: Vec<(f32, Vec2)> = match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+            Some(VertexAttributeValues::Float32x3(positions)) => positions
+                .iter()
+                .map(|position| wave_sample(position[0] + transform.translation.x, position[2] + transform.translation.z, t))
+                .collect(),
+            _ => continue,
+        };
+
+        if let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION)
+        {
+            for (position, (height, _)) in positions.iter_mut().zip(&samples) {
+                position[1] = *height;
+            }
+        }
+        if let Some(VertexAttributeValues::Float32x3(normals)) =
+            mesh.attribute_mut(Mesh::ATTRIBUTE_NORMAL)
+        {
+            for (normal, (_, slope)) in normals.iter_mut().zip(&samples) {
+                *normal = Vec3::new(-slope.x, 1.0, -slope.y).normalize().to_array();
+            }
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use bevy::{prelude::*, pbr::NotShadowCaster};
+use crate::components::{EnemyPlane, Island, Plane, Projectile};
+use crate::constants::*;
+use crate::resources::PlaneState;
+
+// Gun muzzle in plane-local space, just ahead of the nose (the plane flies along -Z)
+const MUZZLE_OFFSET: Vec3 = Vec3::new(0.0, 0.0, -2.5);
+
+// Shared tracer mesh and material, plus the cooldown between shots
+#[derive(Resource)]
+pub struct Weapons {
+    pub mesh: Handle<Mesh>,
+    pub material: Handle<StandardMaterial>,
+    pub cooldown: Timer,
+}
+
+pub fn setup_weapons(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let mut cooldown = Timer::from_seconds(FIRE_COOLDOWN, TimerMode::Once);
+    cooldown.tick(cooldown.duration()); // Ready to fire straight away
+
+    commands.insert_resource(Weapons {
+        mesh: meshes.add(Sphere::new(PROJECTILE_RADIUS).mesh().uv(6, 4)),
+        material: materials.add(StandardMaterial {
+            base_color: Color::srgb(1.0, 0.8, 0.3),
+            emissive: LinearRgba::rgb(12.0, 6.0, 1.0),
+            unlit: true,
+            ..default()
+        }),
+        cooldown,
+    });
+}
+
+// Space fires a tracer from the nose, as often as the cooldown allows
+pub fn fire_input(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+    plane_state: Res<PlaneState>,
+    mut weapons: ResMut<Weapons>,
+    plane_query: Query<&Transform, With<Plane>>,
+) {
+    weapons.cooldown.tick(time.delta());
+    if plane_state.crashed
+        || !keyboard_input.pressed(KeyCode::Space)
+        || !weapons.cooldown.finished()
+    {
+        return;
+    }
+    let Ok(plane_transform) = plane_query.get_single() else {
+        return;
+    };
+
+    weapons.cooldown.reset();
+    let forward = plane_transform.forward();
+    commands.spawn((
+        Mesh3d(weapons.mesh.clone()),
+        MeshMaterial3d(weapons.material.clone()),
+        Transform::from_translation(plane_transform.transform_point(MUZZLE_OFFSET)),
+        NotShadowCaster,
+        Projectile {
+            velocity: plane_state.momentum + forward * MUZZLE_SPEED,
+            lifetime: Timer::from_seconds(PROJECTILE_LIFETIME, TimerMode::Once),
+        },
+    ));
+}
+
+// Fly projectiles and remove them when they time out or hit the water, an island or
+// an enemy plane. An enemy that's hit is shot down.
+#[allow(clippy::type_complexity)]
+pub fn update_projectiles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut projectile_query: Query<(Entity, &mut Transform, &mut Projectile)>,
+    island_query: Query<(&Island, &Transform), Without<Projectile>>,
+    enemy_query: Query<(Entity, &Transform), (With<EnemyPlane>, Without<Projectile>)>,
+) {
+    let dt = time.delta_secs();
+
+    for (entity, mut transform, mut projectile) in projectile_query.iter_mut() {
+        if projectile.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        transform.translation += projectile.velocity * dt;
+        let position = transform.translation;
+
+        // Treat each island as a column as wide as its base and as tall as its top
+        let hit_island = island_query.iter().any(|(island, island_transform)| {
+            position.y < island.kind.top_height() * island_transform.scale.y
+                && position.with_y(0.0).distance(island_transform.translation.with_y(0.0))
+                    < island.kind.footprint_radius() * island_transform.scale.x
+        });
+        let hit_enemy = enemy_query
+            .iter()
+            .find(|(_, enemy)| enemy.translation.distance(position) < PROJECTILE_HIT_RADIUS);
+
+        if let Some((enemy, _)) = hit_enemy {
+            info!("Enemy plane shot down");
+            commands.entity(enemy).despawn_recursive();
+        }
+        if position.y < 0.0 || hit_island || hit_enemy.is_some() {
+            commands.entity(entity).despawn();
+        }
+    }
+}